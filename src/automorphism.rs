@@ -0,0 +1,123 @@
+use crate::canonical::line_permutations;
+use crate::solution::Solution;
+use crate::transform::Transform;
+use itertools::iproduct;
+
+/// Transforms that map a puzzle or grid onto itself
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AutomorphismGroup {
+    /// Every automorphism, starting with the identity.
+    ///
+    /// Digits that don't appear in the puzzle are left unchanged, so each
+    /// rearrangement of cells is listed once.
+    pub transforms: Vec<Transform>,
+}
+
+impl AutomorphismGroup {
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.transforms.len()
+    }
+
+    /// Whether the identity is the only automorphism
+    #[inline]
+    pub fn is_trivial(&self) -> bool {
+        self.size() == 1
+    }
+}
+
+/// Relabelling that makes `grid`, seen through a transposition and line
+/// orders, equal to `target`, if there is one
+#[inline]
+fn matching_labels(grid: &[[u8; 9]; 9], rows: &[u8; 9], cols: &[u8; 9], target: &[[u8; 9]; 9]) -> Option<[u8; 9]> {
+    let mut labels = [9u8; 9];
+    let mut used = [false; 9];
+
+    for (y, x) in iproduct!(0..9, 0..9) {
+        let from = grid[rows[y] as usize][cols[x] as usize];
+        let to = target[y][x];
+        if from == 9 || to == 9 {
+            if from != to {
+                return None;
+            }
+            continue;
+        }
+
+        let label = &mut labels[from as usize];
+        if *label == 9 {
+            if used[to as usize] {
+                return None;
+            }
+            *label = to;
+            used[to as usize] = true;
+        } else if *label != to {
+            return None;
+        }
+    }
+
+    // Both grids use the same digits, so used digits only map onto each other
+    for (digit, label) in labels.iter_mut().enumerate() {
+        if *label == 9 {
+            *label = digit as u8;
+        }
+    }
+
+    Some(labels)
+}
+
+/// Finds every geometric transform and relabelling that leaves `grid`
+/// unchanged
+pub fn automorphisms(grid: &Solution) -> AutomorphismGroup {
+    let mut grids = [[[9u8; 9]; 9]; 2];
+    for (y, x) in iproduct!(0..9, 0..9) {
+        grids[0][y][x] = grid.get(x, y);
+        grids[1][y][x] = grid.get(y, x);
+    }
+
+    let mut transforms = vec![Transform::identity()];
+    for (transpose, source) in grids.iter().enumerate() {
+        for rows in line_permutations() {
+            for cols in line_permutations() {
+                let Some(labels) = matching_labels(source, rows, cols, &grids[0]) else {
+                    continue;
+                };
+                let transform = Transform::new(transpose == 1, *rows, *cols, labels)
+                    .expect("Line permutations keep bands together");
+                if transform != Transform::identity() {
+                    transforms.push(transform);
+                }
+            }
+        }
+    }
+
+    AutomorphismGroup { transforms }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::Transformable;
+
+    const SYMMETRIC: &str = "123456789456789123789123456231564897564897231897231564312645978645978312978312645";
+
+    #[test]
+    fn symmetric_grid_has_large_group() {
+        let grid: Solution = SYMMETRIC.parse().unwrap();
+        let group = automorphisms(&grid);
+
+        assert_eq!(group.size(), 648);
+        assert!(!group.is_trivial());
+        assert_eq!(group.transforms[0], Transform::identity());
+        for transform in &group.transforms {
+            assert_eq!(grid.transformed(transform), grid, "{transform:?}");
+        }
+    }
+
+    #[test]
+    fn typical_grid_is_trivial() {
+        let grid: Solution = "425816973893754621617329548941687235582431769736295184279148356354962817168573492".parse().unwrap();
+        let group = automorphisms(&grid);
+        assert!(group.is_trivial());
+        assert_eq!(group.transforms, [Transform::identity()]);
+    }
+}
@@ -0,0 +1,56 @@
+//! Groups the puzzles in collection files by canonical form, listing every
+//! set of equivalent puzzles.
+//!
+//! Usage: `dedupe <collection files...>`
+
+use color_print::cprintln;
+use std::env;
+use std::process::ExitCode;
+use sudoku_solver_3::{group_equivalent, PuzzleReader};
+
+fn main() -> ExitCode {
+    let paths = env::args().skip(1).collect::<Vec<_>>();
+    if paths.is_empty() {
+        eprintln!("Usage: dedupe <collection files...>");
+        return ExitCode::FAILURE;
+    }
+
+    let mut puzzles = Vec::new();
+    let mut sources = Vec::new();
+    for path in &paths {
+        let reader = match PuzzleReader::open(path) {
+            Ok(reader) => reader,
+            Err(e) => {
+                cprintln!("<r, bold>Failed to open {path}: {e}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+        for entry in reader {
+            match entry {
+                Ok(entry) => {
+                    puzzles.push(entry.puzzle);
+                    sources.push(format!("{path}:{}", entry.line));
+                }
+                Err(e) => cprintln!("<y, bold>Skipping malformed puzzle in {path}: {e}"),
+            }
+        }
+    }
+
+    cprintln!("<b, bold>Canonicalising {} puzzles...", puzzles.len());
+    let classes = group_equivalent(&puzzles);
+    cprintln!(
+        "<g, bold>{} equivalence classes, {} duplicates",
+        classes.len(),
+        puzzles.len() - classes.len()
+    );
+
+    for class in classes.iter().filter(|c| c.members.len() > 1) {
+        println!("{}", class.canonical);
+        for &i in &class.members {
+            println!("  {}", sources[i]);
+        }
+    }
+
+    ExitCode::SUCCESS
+}
@@ -40,11 +40,51 @@ const NUM_LOOKUP: [u8; 257] = [
     255, 255, 255, 255, 255, 255, 255, 8,
 ];
 
+// The 20 cells sharing a row, column or box with each cell, indexed by y * 9 + x
+const PEERS: [[(u8, u8); 20]; 81] = {
+    let mut peers = [[(0, 0); 20]; 81];
+    let mut cell = 0;
+    while cell < 81 {
+        let (x, y) = (cell % 9, cell / 9);
+        let mut found = 0;
+        let mut other = 0;
+        while other < 81 {
+            let (xx, yy) = (other % 9, other / 9);
+            if other != cell && (xx == x || yy == y || (xx / 3 == x / 3 && yy / 3 == y / 3)) {
+                peers[cell][found] = (xx as u8, yy as u8);
+                found += 1;
+            }
+            other += 1;
+        }
+        cell += 1;
+    }
+    peers
+};
+
+/// Whether two different cells share a row, column or box
+#[inline]
+pub fn sees((ax, ay): (usize, usize), (bx, by): (usize, usize)) -> bool {
+    (ax, ay) != (bx, by) && (ax == bx || ay == by || (ax / 3 == bx / 3 && ay / 3 == by / 3))
+}
+
+/// The `(x, y)` positions of the 20 cells sharing a row, column or box with
+/// `(x, y)`, in reading order
+#[inline]
+pub fn peers(x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+    PEERS[y * 9 + x].iter().map(|&(x, y)| (x as usize, y as usize))
+}
+
 type PossibilityMask = u16;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct SudokuPossibility(PossibilityMask);
 
+impl Default for SudokuPossibility {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SudokuPossibility {
     #[inline]
     pub const fn mask(self) -> PossibilityMask {
@@ -67,6 +107,11 @@ impl SudokuPossibility {
         SudokuPossibility(0b00000001_11111111)
     }
 
+    #[inline]
+    pub const fn new_empty() -> Self {
+        SudokuPossibility(0)
+    }
+
     #[inline]
     pub const fn new_val(val: u8) -> Self {
         SudokuPossibility(1 << val)
@@ -87,12 +132,16 @@ impl SudokuPossibility {
         SudokuPossibility(self.0 & mask)
     }
 
+    #[inline]
+    pub const fn or(self, other: SudokuPossibility) -> SudokuPossibility {
+        SudokuPossibility(self.0 | other.0)
+    }
+
     #[inline]
     pub const fn and_mask_inplace(&mut self, mask: PossibilityMask) {
         self.0 &= mask;
     }
 
-    #[allow(dead_code)]
     pub fn slow_find(self) -> Option<u8> {
         let mut found = 9;
         for i in 0..9 {
@@ -176,26 +225,31 @@ impl Possibilities {
 
         let mask: PossibilityMask = !(1 << val);
 
-        let (cell_x, cell_y) = ((x / 3) * 3, (y / 3) * 3);
-        for (yy, xx) in iproduct!(0..3, 0..3) {
-            self.0[cell_y + yy][cell_x + xx].and_mask_inplace(mask);
-        }
-
-        for xx in 0..9 {
-            self.0[y][xx].and_mask_inplace(mask);
-        }
-
-        for yy in 0..9 {
-            self.0[yy][x].and_mask_inplace(mask);
+        self.0[y][x].and_mask_inplace(mask);
+        for &(xx, yy) in &PEERS[y * 9 + x] {
+            self.0[yy as usize][xx as usize].and_mask_inplace(mask);
         }
 
         // self.0[y][x] = SudokuPossibility::new_val(val);
     }
 
+    /// Removes `val` from a cell's possibilities, returning whether it was there
+    #[inline]
+    pub fn remove(&mut self, x: usize, y: usize, val: u8) -> bool {
+        let had = self.0[y][x].has(val);
+        self.0[y][x].and_mask_inplace(!(1 << val));
+        had
+    }
+
     #[inline]
     pub fn get(&self, x: usize, y: usize) -> SudokuPossibility {
         self.0[y][x]
     }
+
+    #[inline]
+    pub fn set(&mut self, x: usize, y: usize, possibility: SudokuPossibility) {
+        self.0[y][x] = possibility;
+    }
 }
 
 impl Display for Possibilities {
@@ -210,11 +264,11 @@ impl Display for Possibilities {
             f,
             "╔═══════════════════════╦═══════════════════════╦═══════════════════════╗"
         )?;
-        for y in 0..9 {
+        for (y, row) in strings.iter().enumerate() {
             for yy in 0..3 {
                 write!(f, "║ ")?;
-                for x in 0..9 {
-                    write!(f, "{}", strings[y][x].lines().nth(yy).unwrap())?;
+                for (x, s) in row.iter().enumerate() {
+                    write!(f, "{}", s.lines().nth(yy).unwrap())?;
                     if x == 8 {
                     } else if (x + 1) % 3 == 0 {
                         write!(f, " ║ ")?;
@@ -270,3 +324,30 @@ impl Debug for Possibilities {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peers_are_the_cells_seen() {
+        for (y, x) in iproduct!(0..9, 0..9) {
+            let cell_peers = peers(x, y).collect_vec();
+            assert_eq!(cell_peers.len(), 20);
+            for (yy, xx) in iproduct!(0..9, 0..9) {
+                assert_eq!(sees((x, y), (xx, yy)), cell_peers.contains(&(xx, yy)), "r{}c{} r{}c{}", y + 1, x + 1, yy + 1, xx + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn update_found_clears_peers() {
+        let mut possibilities = Possibilities::new_full();
+        possibilities.update_found(4, 4, 3);
+
+        for (y, x) in iproduct!(0..9, 0..9) {
+            let seen = (x, y) == (4, 4) || sees((x, y), (4, 4));
+            assert_eq!(possibilities.get(x, y).has(3), !seen, "r{}c{}", y + 1, x + 1);
+        }
+    }
+}
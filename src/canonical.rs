@@ -0,0 +1,191 @@
+use crate::solution::Solution;
+use crate::transform::{Transform, Transformable};
+use itertools::{iproduct, Itertools};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Every ordering of rows (or columns) that keeps bands (or stacks) together
+pub(crate) fn line_permutations() -> &'static [[u8; 9]] {
+    static PERMUTATIONS: OnceLock<Vec<[u8; 9]>> = OnceLock::new();
+    PERMUTATIONS.get_or_init(|| {
+        let orders = (0..3u8).permutations(3).collect_vec();
+        let mut all = Vec::with_capacity(6 * 6 * 6 * 6);
+        for (bands, a, b, c) in iproduct!(&orders, &orders, &orders, &orders) {
+            let within = [a, b, c];
+            let mut lines = [0; 9];
+            for i in 0..9 {
+                lines[i] = bands[i / 3] * 3 + within[i / 3][i % 3];
+            }
+            all.push(lines);
+        }
+        all
+    })
+}
+
+/// Reading-order cell values of `grid` seen through a transposition and line
+/// orders, with digits relabelled `1..=9` in order of first appearance and
+/// empty cells as `0`.
+///
+/// Stops early, returning `None`, as soon as the result is known to be
+/// greater than `best`.
+#[inline]
+fn minlex_candidate(grid: &[[u8; 9]; 9], rows: &[u8; 9], cols: &[u8; 9], best: &[u8; 81]) -> Option<([u8; 81], [u8; 9])> {
+    let mut labels = [0u8; 9];
+    let mut next_label = 1;
+    let mut out = [0; 81];
+    let mut less = false;
+
+    for (i, (y, x)) in iproduct!(0..9, 0..9).enumerate() {
+        let cell = grid[rows[y] as usize][cols[x] as usize];
+        let val = if cell == 9 {
+            0
+        } else {
+            if labels[cell as usize] == 0 {
+                labels[cell as usize] = next_label;
+                next_label += 1;
+            }
+            labels[cell as usize]
+        };
+
+        if !less {
+            if val > best[i] {
+                return None;
+            }
+            less = val < best[i];
+        }
+        out[i] = val;
+    }
+
+    Some((out, labels))
+}
+
+/// Finds the minimal lexicographic (minlex) representative of a puzzle or
+/// grid, along with a transform that maps it there.
+///
+/// Two grids are equivalent, related by one of the 3,359,232 geometric
+/// transforms and 9! relabellings, exactly when their canonical forms are
+/// equal.
+pub fn canonicalize(puzzle: &Solution) -> (Solution, Transform) {
+    let mut grids = [[[9u8; 9]; 9]; 2];
+    for (y, x) in iproduct!(0..9, 0..9) {
+        grids[0][y][x] = puzzle.get(x, y);
+        grids[1][y][x] = puzzle.get(y, x);
+    }
+
+    let mut best = [u8::MAX; 81];
+    let mut best_transform = None;
+    for (transpose, grid) in grids.iter().enumerate() {
+        for rows in line_permutations() {
+            for cols in line_permutations() {
+                if let Some((out, labels)) = minlex_candidate(grid, rows, cols, &best) {
+                    best = out;
+                    best_transform = Some((transpose == 1, *rows, *cols, labels));
+                }
+            }
+        }
+    }
+
+    let (transpose, rows, cols, mut labels) = best_transform.expect("The first candidate is always accepted");
+    // Digits missing from the puzzle still need somewhere to go
+    let mut unused = (1..=9).filter(|l| !labels.contains(l)).collect_vec().into_iter();
+    for label in labels.iter_mut() {
+        if *label == 0 {
+            *label = unused.next().unwrap();
+        }
+    }
+
+    let transform = Transform::new(transpose, rows, cols, labels.map(|l| l - 1))
+        .expect("Line permutations keep bands together");
+    (puzzle.transformed(&transform), transform)
+}
+
+/// Minimal lexicographic representative of a puzzle's equivalence class
+pub fn canonical_form(puzzle: &Solution) -> Solution {
+    canonicalize(puzzle).0
+}
+
+/// Puzzles sharing a canonical form
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EquivalenceClass {
+    pub canonical: Solution,
+    /// Indices of the equivalent puzzles, in input order
+    pub members: Vec<usize>,
+}
+
+/// Groups puzzles by canonical form, in order of first appearance
+pub fn group_equivalent(puzzles: &[Solution]) -> Vec<EquivalenceClass> {
+    let forms = puzzles.par_iter().map(canonical_form).collect::<Vec<_>>();
+
+    let mut classes: Vec<EquivalenceClass> = Vec::new();
+    let mut index: HashMap<&Solution, usize> = HashMap::new();
+    for (i, form) in forms.iter().enumerate() {
+        match index.get(form) {
+            Some(&class) => classes[class].members.push(i),
+            None => {
+                index.insert(form, classes.len());
+                classes.push(EquivalenceClass {
+                    canonical: form.clone(),
+                    members: vec![i],
+                });
+            }
+        }
+    }
+
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = ".2...69......5..2.6..3.....94...7......4..7...3.2...8...9.4....3..9.2.17..8.....2";
+    const OTHER: &str = "9.7...6511.5.9..8...........5.4......2.....4...1..6.....86...3...41..9....9.5..6.";
+
+    fn grid(line: &str) -> Solution {
+        line.parse().unwrap()
+    }
+
+    fn transforms() -> Vec<Transform> {
+        vec![
+            Transform::rotate90().then(&Transform::relabel([3, 1, 4, 0, 5, 8, 2, 6, 7])),
+            Transform::transpose()
+                .then(&Transform::permute_bands([2, 0, 1]))
+                .then(&Transform::permute_columns_in_stack(1, [2, 1, 0])),
+            Transform::mirror_vertical()
+                .then(&Transform::permute_rows_in_band(0, [1, 2, 0]))
+                .then(&Transform::permute_stacks([1, 0, 2]))
+                .then(&Transform::relabel([8, 7, 6, 5, 4, 3, 2, 1, 0])),
+        ]
+    }
+
+    #[test]
+    fn canonical_form_is_invariant() {
+        let puzzle = grid(PUZZLE);
+        let form = canonical_form(&puzzle);
+        for transform in transforms() {
+            assert_eq!(canonical_form(&puzzle.transformed(&transform)), form, "{transform:?}");
+        }
+    }
+
+    #[test]
+    fn canonicalize_returns_its_transform() {
+        for puzzle in [grid(PUZZLE), grid(PUZZLE).transformed(&transforms()[2])] {
+            let (form, transform) = canonicalize(&puzzle);
+            assert_eq!(puzzle.transformed(&transform), form);
+        }
+    }
+
+    #[test]
+    fn group_equivalent_separates_classes() {
+        let puzzle = grid(PUZZLE);
+        let puzzles = [puzzle.clone(), grid(OTHER), puzzle.transformed(&transforms()[1])];
+
+        let classes = group_equivalent(&puzzles);
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes[0].members, [0, 2]);
+        assert_eq!(classes[0].canonical, canonical_form(&puzzle));
+        assert_eq!(classes[1].members, [1]);
+        assert_eq!(classes[1].canonical, canonical_form(&grid(OTHER)));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::error::{LoadError, ParseError};
+use crate::solution::Solution;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Lines};
+use std::path::Path;
+
+/// What a collection line says about a puzzle's solution
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolutionColumn {
+    Given(Solution),
+    /// Marked `!`, the puzzle has no unique solution
+    NotUnique,
+    /// The line has no solution column
+    Missing,
+}
+
+impl SolutionColumn {
+    /// The given solution, if there is one
+    pub fn solution(&self) -> Option<&Solution> {
+        match self {
+            SolutionColumn::Given(solution) => Some(solution),
+            SolutionColumn::NotUnique | SolutionColumn::Missing => None,
+        }
+    }
+}
+
+/// A puzzle read from a collection file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PuzzleEntry {
+    /// 1-based line the puzzle was read from
+    pub line: usize,
+    pub puzzle: Solution,
+    pub solution: SolutionColumn,
+    pub comment: Option<String>,
+}
+
+impl PuzzleEntry {
+    /// Parses a collection line: an 81-character puzzle, optionally followed
+    /// by an 81-character solution or `!`, then an optional comment
+    pub fn parse(line: &str, line_number: usize) -> Result<Self, ParseError> {
+        let (puzzle, rest) = Solution::parse_line(line).map_err(|e| e.at_line(line_number))?;
+        let rest = rest.unwrap_or("");
+
+        let (solution, comment) = match rest.split_once(char::is_whitespace).unwrap_or((rest, "")) {
+            ("!", comment) => (SolutionColumn::NotUnique, comment),
+            (column, comment) if column.chars().count() == 81 => {
+                // The solution column starts after the puzzle and the whitespace before it
+                let offset = 81 + line.chars().skip(81).take_while(|c| c.is_whitespace()).count();
+                let solution =
+                    Solution::from_line(column).map_err(|e| e.after_column(offset).at_line(line_number))?;
+                (SolutionColumn::Given(solution), comment)
+            }
+            _ => (SolutionColumn::Missing, rest),
+        };
+
+        let comment = comment.trim();
+        Ok(PuzzleEntry {
+            line: line_number,
+            puzzle,
+            solution,
+            comment: (!comment.is_empty()).then(|| comment.to_string()),
+        })
+    }
+
+    /// Writes the entry back out as a collection line
+    pub fn to_line(&self) -> String {
+        let mut line = self.puzzle.to_line();
+        match &self.solution {
+            SolutionColumn::Given(solution) => {
+                line.push(' ');
+                line += &solution.to_line();
+            }
+            SolutionColumn::NotUnique => line += " !",
+            SolutionColumn::Missing => {}
+        }
+        if let Some(comment) = &self.comment {
+            line.push(' ');
+            line += comment;
+        }
+        line
+    }
+}
+
+/// Lazily reads a file of single-line puzzles, one per line.
+///
+/// Blank lines and lines starting with `#` are skipped. A malformed line
+/// produces an error carrying its line number, and reading carries on with
+/// the next line.
+pub struct PuzzleReader<R> {
+    lines: Lines<R>,
+    line: usize,
+}
+
+impl PuzzleReader<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: BufRead> PuzzleReader<R> {
+    pub fn new(reader: R) -> Self {
+        PuzzleReader {
+            lines: reader.lines(),
+            line: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PuzzleReader<R> {
+    type Item = Result<PuzzleEntry, LoadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Count the line before matching, as a failed read still consumes it
+            self.line += 1;
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(error) => return Some(Err(LoadError::Read { line: self.line, error })),
+            };
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+            return Some(
+                PuzzleEntry::parse(trimmed, self.line).map_err(|e| LoadError::from(e.after_column(indent))),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......";
+    const SOLUTION: &str = "417369825632158947958724316825437169791586432346912758289643571573291684164875293";
+
+    fn read(contents: &[u8]) -> Vec<Result<PuzzleEntry, LoadError>> {
+        PuzzleReader::new(contents).collect()
+    }
+
+    #[test]
+    fn entries_round_trip() {
+        for line in [
+            PUZZLE.to_string(),
+            format!("{PUZZLE} {SOLUTION}"),
+            format!("{PUZZLE} ! no unique solution"),
+            format!("{PUZZLE} {SOLUTION} # from a collection"),
+        ] {
+            let entry = PuzzleEntry::parse(&line, 1).unwrap();
+            assert_eq!(PuzzleEntry::parse(&entry.to_line(), 1).unwrap(), entry);
+        }
+
+        let entry = PuzzleEntry::parse(&format!("{PUZZLE} {SOLUTION} comment"), 1).unwrap();
+        assert_eq!(entry.puzzle.to_line(), PUZZLE);
+        assert_eq!(entry.solution.solution().unwrap().to_line(), SOLUTION);
+        assert_eq!(entry.comment.as_deref(), Some("comment"));
+    }
+
+    #[test]
+    fn not_unique_is_kept_apart_from_missing() {
+        let marked = PuzzleEntry::parse(&format!("{PUZZLE} ! no unique solution"), 1).unwrap();
+        assert_eq!(marked.solution, SolutionColumn::NotUnique);
+        assert_eq!(marked.comment.as_deref(), Some("no unique solution"));
+        assert_eq!(marked.to_line(), format!("{PUZZLE} ! no unique solution"));
+
+        let missing = PuzzleEntry::parse(&format!("{PUZZLE} no unique solution"), 1).unwrap();
+        assert_eq!(missing.solution, SolutionColumn::Missing);
+        assert_eq!(missing.to_line(), format!("{PUZZLE} no unique solution"));
+
+        let bare = PuzzleEntry::parse(&format!("{PUZZLE} !"), 1).unwrap();
+        assert_eq!(bare.solution, SolutionColumn::NotUnique);
+        assert_eq!(bare.comment, None);
+        assert_eq!(PuzzleEntry::parse(&bare.to_line(), 1).unwrap(), bare);
+    }
+
+    #[test]
+    fn skips_blank_and_comment_lines() {
+        let contents = format!("# header\n\n{PUZZLE}\n   \n{PUZZLE} {SOLUTION}\n");
+        let lines = read(contents.as_bytes()).into_iter().map(|e| e.unwrap().line).collect::<Vec<_>>();
+        assert_eq!(lines, [3, 5]);
+    }
+
+    #[test]
+    fn errors_in_the_solution_column_report_their_column() {
+        let line = format!("{PUZZLE}  {}", "x".repeat(81));
+        assert_eq!(
+            PuzzleEntry::parse(&line, 4),
+            Err(ParseError::UnexpectedCharacter { line: 4, column: 84, found: 'x' })
+        );
+    }
+
+    #[test]
+    fn errors_in_indented_lines_report_their_column() {
+        let contents = format!("  {}x\n", &PUZZLE[..80]);
+        match &read(contents.as_bytes())[..] {
+            [Err(LoadError::Parse(e))] => {
+                assert_eq!(*e, ParseError::UnexpectedCharacter { line: 1, column: 83, found: 'x' })
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unreadable_lines_keep_line_numbers() {
+        let mut contents = vec![0xff, 0xfe, b'\n'];
+        contents.extend(format!("{PUZZLE}\nbad\n").bytes());
+
+        match &read(&contents)[..] {
+            [Err(LoadError::Read { line: 1, .. }), Ok(entry), Err(LoadError::Parse(e))] => {
+                assert_eq!(entry.line, 2);
+                assert_eq!(*e, ParseError::UnexpectedCharacter { line: 3, column: 1, found: 'b' });
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::validation::ValidationReport;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+/// Error produced when a puzzle's text cannot be parsed.
+///
+/// Lines and columns are 1-based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    TooManyLines { line: usize },
+    LineTooLong { line: usize, column: usize },
+    UnexpectedCharacter { line: usize, column: usize, found: char },
+    /// A single-line puzzle ended before all 81 cells were given
+    TooFewCells { line: usize, found: usize },
+    /// A pencil-mark row didn't have 9 cells
+    WrongCellCount { line: usize, found: usize },
+    /// Fewer than 9 rows of pencil marks
+    TooFewRows { found: usize },
+}
+
+impl ParseError {
+    /// Moves the error to `line`, for text parsed out of a larger file
+    pub(crate) fn at_line(self, line: usize) -> Self {
+        match self {
+            ParseError::TooManyLines { .. } => ParseError::TooManyLines { line },
+            ParseError::LineTooLong { column, .. } => ParseError::LineTooLong { line, column },
+            ParseError::UnexpectedCharacter { column, found, .. } => {
+                ParseError::UnexpectedCharacter { line, column, found }
+            }
+            ParseError::TooFewCells { found, .. } => ParseError::TooFewCells { line, found },
+            ParseError::WrongCellCount { found, .. } => ParseError::WrongCellCount { line, found },
+            ParseError::TooFewRows { found } => ParseError::TooFewRows { found },
+        }
+    }
+
+    /// Shifts the error's column right by `offset`, for text parsed out of
+    /// the middle of a line
+    pub(crate) fn after_column(self, offset: usize) -> Self {
+        match self {
+            ParseError::LineTooLong { line, column } => ParseError::LineTooLong { line, column: column + offset },
+            ParseError::UnexpectedCharacter { line, column, found } => {
+                ParseError::UnexpectedCharacter { line, column: column + offset, found }
+            }
+            other => other,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::TooManyLines { line } => {
+                write!(f, "too many lines, unexpected content on line {line}")
+            }
+            ParseError::LineTooLong { line, column } => {
+                write!(f, "line {line} too long, unexpected content at column {column}")
+            }
+            ParseError::UnexpectedCharacter { line, column, found } => {
+                write!(f, "expected number, found '{found}' at line {line}, column {column}")
+            }
+            ParseError::TooFewCells { line, found } => {
+                write!(f, "expected 81 cells on line {line}, found {found}")
+            }
+            ParseError::WrongCellCount { line, found } => {
+                write!(f, "expected 9 cells on line {line}, found {found}")
+            }
+            ParseError::TooFewRows { found } => {
+                write!(f, "expected 9 rows, found {found}")
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Error produced when a puzzle cannot be loaded from a file or text.
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    /// Reading a line of a multi-puzzle file failed, e.g. on invalid UTF-8
+    Read { line: usize, error: io::Error },
+    Parse(ParseError),
+    /// The text parsed, but its filled cells clash with each other
+    Invalid(ValidationReport),
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "file read error: {e}"),
+            LoadError::Read { line, error } => write!(f, "file read error on line {line}: {error}"),
+            LoadError::Parse(e) => write!(f, "{e}"),
+            LoadError::Invalid(report) => write!(f, "invalid grid: {report}"),
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Io(e) => Some(e),
+            LoadError::Read { error, .. } => Some(error),
+            LoadError::Parse(e) => Some(e),
+            LoadError::Invalid(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(e: io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+impl From<ParseError> for LoadError {
+    fn from(e: ParseError) -> Self {
+        LoadError::Parse(e)
+    }
+}
@@ -0,0 +1,289 @@
+use crate::rating::{rate, Grade, Rating};
+use crate::solution::Solution;
+use crate::solver::{has_unique_solution, solve_backtracking};
+use itertools::iproduct;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Fills the diagonal boxes randomly and completes the grid by backtracking
+pub(crate) fn random_full_grid<R: Rng>(rng: &mut R) -> Solution {
+    let mut grid = Solution::new_empty();
+
+    // Boxes on the diagonal don't constrain each other, so any filling of
+    // them can be completed
+    for b in 0..3 {
+        let mut digits: [u8; 9] = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+        digits.shuffle(rng);
+        for (i, digit) in digits.into_iter().enumerate() {
+            grid.set(b * 3 + i % 3, b * 3 + i / 3, digit);
+        }
+    }
+
+    solve_backtracking(grid).expect("Diagonal boxes should always be completable")
+}
+
+/// A generated puzzle along with its unique solution
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeneratedPuzzle {
+    pub puzzle: Solution,
+    pub solution: Solution,
+}
+
+/// Symmetry of the layout of a generated puzzle's clues
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    #[default]
+    None,
+    Rotational180,
+    Rotational90,
+    /// Mirrored top to bottom, across the middle row
+    Horizontal,
+    /// Mirrored left to right, across the middle column
+    Vertical,
+    /// Mirrored across the top-left to bottom-right diagonal
+    Diagonal,
+}
+
+impl Symmetry {
+    pub const ALL: &'static [Symmetry] = &[
+        Symmetry::None,
+        Symmetry::Rotational180,
+        Symmetry::Rotational90,
+        Symmetry::Horizontal,
+        Symmetry::Vertical,
+        Symmetry::Diagonal,
+    ];
+
+    /// Cells that must be filled or empty together with `(x, y)`, including it
+    pub fn orbit(self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut cells = match self {
+            Symmetry::None => vec![(x, y)],
+            Symmetry::Rotational180 => vec![(x, y), (8 - x, 8 - y)],
+            Symmetry::Rotational90 => vec![(x, y), (8 - y, x), (8 - x, 8 - y), (y, 8 - x)],
+            Symmetry::Horizontal => vec![(x, y), (x, 8 - y)],
+            Symmetry::Vertical => vec![(x, y), (8 - x, y)],
+            Symmetry::Diagonal => vec![(x, y), (y, x)],
+        };
+        cells.sort();
+        cells.dedup();
+        cells
+    }
+}
+
+/// Difficulty a generated puzzle should be rated at
+#[derive(Clone, Debug, PartialEq)]
+pub enum DifficultyTarget {
+    Grade(Grade),
+    /// Solvable by logic, with a difficulty in the inclusive range
+    Difficulty(f32, f32),
+}
+
+impl DifficultyTarget {
+    pub fn accepts(&self, rating: &Rating) -> bool {
+        match self {
+            DifficultyTarget::Grade(grade) => rating.grade() == *grade,
+            DifficultyTarget::Difficulty(min, max) => {
+                rating.solved && (*min..=*max).contains(&rating.difficulty)
+            }
+        }
+    }
+
+    /// Whether removing more clues can't bring the rating back into range
+    fn exceeded_by(&self, rating: &Rating) -> bool {
+        match self {
+            DifficultyTarget::Grade(grade) => rating.grade() > *grade,
+            DifficultyTarget::Difficulty(_, max) => !rating.solved || rating.difficulty > *max,
+        }
+    }
+}
+
+/// A generated puzzle that met a [`DifficultyTarget`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetedPuzzle {
+    pub generated: GeneratedPuzzle,
+    pub rating: Rating,
+    /// Number of grids tried, including the successful one
+    pub attempts: usize,
+}
+
+/// Generates puzzles with a unique solution.
+///
+/// The same seed always produces the same sequence of puzzles.
+pub struct Generator {
+    rng: ChaCha8Rng,
+    symmetry: Symmetry,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Self {
+        Generator {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            symmetry: Symmetry::None,
+        }
+    }
+
+    /// Keeps the clue layout of generated puzzles symmetric
+    pub fn with_symmetry(mut self, symmetry: Symmetry) -> Self {
+        self.symmetry = symmetry;
+        self
+    }
+
+    /// Builds a random completed grid
+    pub fn full_grid(&mut self) -> Solution {
+        random_full_grid(&mut self.rng)
+    }
+
+    /// Generates a puzzle from a new random grid
+    pub fn generate(&mut self) -> GeneratedPuzzle {
+        let solution = self.full_grid();
+        let puzzle = self.remove_clues(&solution);
+        GeneratedPuzzle { puzzle, solution }
+    }
+
+    /// Generates puzzles until one is rated within `target`.
+    ///
+    /// Clues are only removed while the puzzle stays within reach of the
+    /// target, so easy targets aren't overshot. Gives up after `max_attempts`
+    /// grids.
+    pub fn generate_rated(&mut self, target: &DifficultyTarget, max_attempts: usize) -> Option<TargetedPuzzle> {
+        for attempts in 1..=max_attempts {
+            let solution = self.full_grid();
+            let puzzle = self.remove_clues_while(&solution, |puzzle| {
+                rate(puzzle).is_ok_and(|rating| !target.exceeded_by(&rating))
+            });
+
+            let rating = rate(&puzzle).expect("Generated puzzles should be valid");
+            if target.accepts(&rating) {
+                return Some(TargetedPuzzle {
+                    generated: GeneratedPuzzle { puzzle, solution },
+                    rating,
+                    attempts,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Removes clues in random order for as long as the solution stays unique
+    pub fn remove_clues(&mut self, solution: &Solution) -> Solution {
+        self.remove_clues_while(solution, |_| true)
+    }
+
+    /// Removes clues in random order while the solution stays unique and
+    /// `keep` accepts the resulting puzzle.
+    ///
+    /// Clues are removed a whole symmetry orbit at a time.
+    pub fn remove_clues_while<F: FnMut(&Solution) -> bool>(&mut self, solution: &Solution, mut keep: F) -> Solution {
+        let mut cells = iproduct!(0..9, 0..9).collect::<Vec<(usize, usize)>>();
+        cells.shuffle(&mut self.rng);
+
+        let mut puzzle = solution.clone();
+        for (x, y) in cells {
+            if puzzle.get(x, y) == 9 {
+                continue;
+            }
+
+            let orbit = self.symmetry.orbit(x, y);
+            let vals = orbit.iter().map(|&(x, y)| puzzle.get(x, y)).collect::<Vec<_>>();
+            for &(x, y) in &orbit {
+                puzzle.set(x, y, 9);
+            }
+
+            if !has_unique_solution(puzzle.clone()) || !keep(&puzzle) {
+                for (&(x, y), &val) in orbit.iter().zip(&vals) {
+                    puzzle.set(x, y, val);
+                }
+            }
+        }
+
+        puzzle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks `generated` has a unique solution whose givens it keeps
+    fn assert_generated(generated: &GeneratedPuzzle) {
+        let GeneratedPuzzle { puzzle, solution } = generated;
+        assert!(solution.solved() && solution.is_valid());
+        assert!(has_unique_solution(puzzle.clone()));
+        assert_eq!(solve_backtracking(puzzle.clone()).as_ref(), Some(solution));
+        for (y, x) in iproduct!(0..9, 0..9) {
+            assert!(puzzle.get(x, y) == 9 || puzzle.get(x, y) == solution.get(x, y));
+        }
+    }
+
+    #[test]
+    fn same_seed_generates_same_puzzles() {
+        let mut a = Generator::new(7);
+        let mut b = Generator::new(7);
+        for _ in 0..3 {
+            assert_eq!(a.generate(), b.generate());
+        }
+        assert_ne!(Generator::new(7).generate(), Generator::new(8).generate());
+    }
+
+    #[test]
+    fn generated_puzzles_are_unique() {
+        let mut generator = Generator::new(42);
+        for _ in 0..5 {
+            assert_generated(&generator.generate());
+        }
+    }
+
+    #[test]
+    fn generate_rated_hits_target() {
+        for target in [
+            DifficultyTarget::Difficulty(1.0, 1.5),
+            DifficultyTarget::Grade(Grade::Medium),
+            DifficultyTarget::Grade(Grade::Hard),
+        ] {
+            let result = Generator::new(3).generate_rated(&target, 100).unwrap();
+            assert!(target.accepts(&result.rating), "{target:?} {}", result.rating);
+            assert!(result.attempts >= 1);
+            assert_eq!(rate(&result.generated.puzzle).unwrap(), result.rating);
+            assert_generated(&result.generated);
+        }
+    }
+
+    #[test]
+    fn generate_rated_gives_up() {
+        let target = DifficultyTarget::Difficulty(9.0, 10.0);
+        assert_eq!(Generator::new(3).generate_rated(&target, 2), None);
+        assert_eq!(Generator::new(3).generate_rated(&DifficultyTarget::Grade(Grade::Easy), 0), None);
+    }
+
+    #[test]
+    fn symmetric_layouts() {
+        for &symmetry in Symmetry::ALL {
+            let mut generator = Generator::new(11).with_symmetry(symmetry);
+            for _ in 0..2 {
+                let generated = generator.generate();
+                assert_generated(&generated);
+
+                for (y, x) in iproduct!(0..9, 0..9) {
+                    let filled = generated.puzzle.get(x, y) != 9;
+                    for (ox, oy) in symmetry.orbit(x, y) {
+                        assert_eq!(generated.puzzle.get(ox, oy) != 9, filled, "{symmetry:?} r{}c{}", y + 1, x + 1);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn orbits() {
+        assert_eq!(Symmetry::None.orbit(1, 2), [(1, 2)]);
+        assert_eq!(Symmetry::Rotational180.orbit(1, 2), [(1, 2), (7, 6)]);
+        assert_eq!(Symmetry::Rotational90.orbit(1, 2), [(1, 2), (2, 7), (6, 1), (7, 6)]);
+        assert_eq!(Symmetry::Rotational90.orbit(4, 4), [(4, 4)]);
+        assert_eq!(Symmetry::Horizontal.orbit(1, 2), [(1, 2), (1, 6)]);
+        assert_eq!(Symmetry::Vertical.orbit(1, 2), [(1, 2), (7, 2)]);
+        assert_eq!(Symmetry::Diagonal.orbit(1, 2), [(1, 2), (2, 1)]);
+        assert_eq!(Symmetry::Diagonal.orbit(3, 3), [(3, 3)]);
+    }
+}
@@ -0,0 +1,44 @@
+//! Backtracking sudoku solver.
+//!
+//! Digits are stored zero-indexed (`0..=8`), with `9` marking an empty cell.
+
+mod automorphism;
+mod board;
+mod canonical;
+mod collection;
+mod error;
+mod generator;
+mod logical;
+mod minimal;
+mod pencil_marks;
+mod rating;
+mod sampler;
+mod solution;
+mod solutions;
+mod solver;
+mod techniques;
+mod transform;
+mod validation;
+
+pub use automorphism::{automorphisms, AutomorphismGroup};
+pub use board::{peers, sees, Possibilities, SudokuPossibility};
+pub use canonical::{canonical_form, canonicalize, group_equivalent, EquivalenceClass};
+pub use collection::{PuzzleEntry, PuzzleReader, SolutionColumn};
+pub use error::{LoadError, ParseError};
+pub use generator::{DifficultyTarget, GeneratedPuzzle, Generator, Symmetry, TargetedPuzzle};
+pub use logical::{next_hint, LogicalSolver, SolvingPath};
+pub use minimal::{check_minimality, minimize, MinimalityReport};
+pub use rating::{rate, rate_with, Grade, Rating};
+pub use sampler::GridSampler;
+pub use solution::Solution;
+pub use solutions::Solutions;
+pub use solver::{
+    count_solutions, has_unique_solution, recursively_attempt, solutions, solve, solve_backtracking,
+    solve_backtracking_with, SolveOutcome,
+};
+pub use techniques::{find_finned_fish, find_fish, Candidate, Fish, FishKind, Step, Technique};
+pub use transform::{Transform, Transformable};
+pub use validation::{Duplicate, Unit, ValidationReport};
+
+/// Version of the public API, taken from the crate version.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
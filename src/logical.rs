@@ -0,0 +1,259 @@
+use crate::board::Possibilities;
+use crate::solution::Solution;
+use crate::techniques::{Step, Technique};
+use crate::validation::ValidationReport;
+use itertools::iproduct;
+use std::fmt::{Display, Formatter};
+
+/// Solves puzzles using only logical techniques, recording each deduction.
+#[derive(Clone)]
+pub struct LogicalSolver {
+    solution: Solution,
+    possibilities: Possibilities,
+    techniques: Vec<Technique>,
+}
+
+impl LogicalSolver {
+    /// Creates a solver using every technique, failing if the givens clash
+    pub fn new(puzzle: &Solution) -> Result<Self, ValidationReport> {
+        let report = puzzle.validate();
+        if !report.is_valid() {
+            return Err(report);
+        }
+
+        Ok(LogicalSolver {
+            solution: puzzle.clone(),
+            possibilities: Possibilities::from_solution(puzzle),
+            techniques: Technique::ALL.to_vec(),
+        })
+    }
+
+    /// Creates a solver resuming from a candidate grid, taking cells with a
+    /// single candidate as filled
+    pub fn from_possibilities(mut possibilities: Possibilities) -> Result<Self, ValidationReport> {
+        let mut solution = Solution::new_empty();
+        for (y, x) in iproduct!(0..9, 0..9) {
+            let cell = possibilities.get(x, y);
+            if cell.bits_set() == 1 {
+                solution.set(x, y, cell.find_single_bit());
+            }
+        }
+
+        let report = solution.validate();
+        if !report.is_valid() {
+            return Err(report);
+        }
+
+        for (y, x) in iproduct!(0..9, 0..9) {
+            let val = solution.get(x, y);
+            if val != 9 {
+                possibilities.update_found(x, y, val);
+            }
+        }
+
+        Ok(LogicalSolver {
+            solution,
+            possibilities,
+            techniques: Technique::ALL.to_vec(),
+        })
+    }
+
+    /// Restricts the solver to `techniques`, tried in increasing difficulty
+    pub fn with_techniques(mut self, techniques: &[Technique]) -> Self {
+        self.techniques = techniques.to_vec();
+        self.techniques.sort();
+        self.techniques.dedup();
+        self
+    }
+
+    #[inline]
+    pub fn solution(&self) -> &Solution {
+        &self.solution
+    }
+
+    #[inline]
+    pub fn possibilities(&self) -> &Possibilities {
+        &self.possibilities
+    }
+
+    /// Finds the simplest available step without applying it
+    pub fn find_step(&self) -> Option<Step> {
+        self.techniques
+            .iter()
+            .find_map(|t| t.find(&self.solution, &self.possibilities))
+    }
+
+    pub fn apply(&mut self, step: &Step) {
+        for c in &step.placements {
+            self.solution.set(c.x, c.y, c.digit);
+            self.possibilities.update_found(c.x, c.y, c.digit);
+        }
+        for c in &step.eliminations {
+            self.possibilities.remove(c.x, c.y, c.digit);
+        }
+    }
+
+    /// Finds and applies the simplest available step
+    pub fn step(&mut self) -> Option<Step> {
+        let step = self.find_step()?;
+        self.apply(&step);
+        Some(step)
+    }
+
+    /// Applies steps until the puzzle is solved or no technique applies
+    pub fn solve(mut self) -> SolvingPath {
+        let mut steps = Vec::new();
+        while !self.solution.solved() {
+            let Some(step) = self.step() else { break; };
+            steps.push(step);
+        }
+
+        SolvingPath {
+            steps,
+            solution: self.solution,
+        }
+    }
+}
+
+/// Finds the simplest deduction available on a partially filled grid.
+///
+/// Only the next step is returned, not the rest of the solution. `Ok(None)`
+/// means no known technique applies, and clashing givens are reported as an
+/// error.
+pub fn next_hint(solution: &Solution) -> Result<Option<Step>, ValidationReport> {
+    Ok(LogicalSolver::new(solution)?.find_step())
+}
+
+/// Every step taken by a [`LogicalSolver`] and the grid it ended on
+#[derive(Clone, Debug)]
+pub struct SolvingPath {
+    pub steps: Vec<Step>,
+    pub solution: Solution,
+}
+
+impl SolvingPath {
+    #[inline]
+    pub fn solved(&self) -> bool {
+        self.solution.solved()
+    }
+}
+
+impl Display for SolvingPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width = self.steps.len().to_string().len();
+        for (i, step) in self.steps.iter().enumerate() {
+            writeln!(f, "{:>width$}. {step}", i + 1)?;
+        }
+        if self.solved() {
+            write!(f, "Solved")
+        } else {
+            write!(f, "Stuck, no technique applies")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::techniques::Candidate;
+
+    const MEDIUM: &str = ".85.13..9634..2175.2.574.3.24836795196.458.233572..48.5731..892496.2531.812.39564";
+    const MEDIUM_SOLUTION: &str = "785613249634982175129574638248367951961458723357291486573146892496825317812739564";
+    const HARD: &str = ".2...69......5..2.6..3.....94...7......4..7...3.2...8...9.4....3..9.2.17..8.....2";
+    const VERY_EASY: &str = "1.43829562.54671383869514.24615238977381496259528763145296347816.7298543843.15269";
+    const POINTING: &str = "9.7...6511.5.9..8...........5.4......2.....4...1..6.....86...3...41..9....9.5..6.";
+    const HARD_SOLUTION: &str = "425816973893754621617329548941687235582431769736295184279148356354962817168573492";
+
+    fn grid(line: &str) -> Solution {
+        line.parse().unwrap()
+    }
+
+    /// Checks every step of the path against the known solution
+    fn assert_sound(path: &SolvingPath, solution: &Solution) {
+        for step in &path.steps {
+            for c in &step.placements {
+                assert_eq!(solution.get(c.x, c.y), c.digit, "{step}");
+            }
+            for c in &step.eliminations {
+                assert_ne!(solution.get(c.x, c.y), c.digit, "{step}");
+            }
+        }
+    }
+
+    #[test]
+    fn solving_path_reaches_the_solution() {
+        for (puzzle, solution) in [(MEDIUM, MEDIUM_SOLUTION), (HARD, HARD_SOLUTION)] {
+            let path = LogicalSolver::new(&grid(puzzle)).unwrap().solve();
+            assert!(path.solved());
+            assert_eq!(path.solution, grid(solution));
+            assert_sound(&path, &grid(solution));
+
+            let placed: usize = path.steps.iter().map(|s| s.placements.len()).sum();
+            assert_eq!(placed, 81 - grid(puzzle).clue_count());
+        }
+    }
+
+    #[test]
+    fn singles_only_solver_stops_when_stuck() {
+        let solver = LogicalSolver::new(&grid(HARD))
+            .unwrap()
+            .with_techniques(&[Technique::NakedSingle, Technique::HiddenSingle]);
+        let path = solver.solve();
+
+        assert!(!path.solved());
+        assert_sound(&path, &grid(HARD_SOLUTION));
+    }
+
+    #[test]
+    fn clashing_givens_are_rejected() {
+        let mut puzzle = grid(MEDIUM);
+        puzzle.set(0, 0, 7);
+        assert!(LogicalSolver::new(&puzzle).is_err());
+    }
+
+    #[test]
+    fn hint_prefers_naked_single() {
+        let puzzle = grid(VERY_EASY);
+        let solver = LogicalSolver::new(&puzzle).unwrap();
+        assert!(Technique::HiddenSingle.find(solver.solution(), solver.possibilities()).is_some());
+
+        let hint = next_hint(&puzzle).unwrap().unwrap();
+        assert_eq!(hint.technique, Technique::NakedSingle);
+        assert_eq!(hint.placements, [Candidate::new(1, 0, 6)]);
+    }
+
+    #[test]
+    fn hint_falls_back_to_hidden_single() {
+        let puzzle = grid(HARD);
+        let solver = LogicalSolver::new(&puzzle).unwrap();
+        assert_eq!(Technique::NakedSingle.find(solver.solution(), solver.possibilities()), None);
+
+        let hint = next_hint(&puzzle).unwrap().unwrap();
+        assert_eq!(hint.technique, Technique::HiddenSingle);
+        let c = hint.placements[0];
+        assert_eq!(grid(HARD_SOLUTION).get(c.x, c.y), c.digit);
+    }
+
+    #[test]
+    fn hint_uses_harder_techniques_once_singles_run_out() {
+        let mut solver = LogicalSolver::new(&grid(POINTING)).unwrap();
+        let step = loop {
+            let step = solver.find_step().unwrap();
+            if step.technique > Technique::HiddenSingle {
+                break step;
+            }
+            solver.apply(&step);
+        };
+
+        let hint = next_hint(solver.solution()).unwrap().unwrap();
+        assert_eq!(hint.technique, Technique::LockedPointing);
+        assert_eq!(hint, step);
+    }
+
+    #[test]
+    fn hint_rejects_clashing_givens() {
+        let mut puzzle = grid(VERY_EASY);
+        puzzle.set(1, 0, 0);
+        assert!(next_hint(&puzzle).is_err());
+    }
+}
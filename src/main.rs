@@ -1,22 +1,20 @@
 use std::cmp::max;
-use crate::solver::solve_backtracking;
 use std::hint::black_box;
 use std::io::{stdout, Write};
 use std::time::Instant;
 use color_print::{cprint, cprintln};
 use itertools::Itertools;
 use rayon::prelude::*;
+use sudoku_solver_3::{has_unique_solution, rate, solve_backtracking, solve_backtracking_with, Grade, SolutionColumn, Technique};
 use thousands::Separable;
 
-mod board;
-mod solution;
-mod solver;
 mod util;
 
 const INDIVIDUAL_RUNS: usize = 100_000;
 const MIXED_RUNS: usize = 100_000;
 const INDIVIDUAL_PAR_RUNS: usize = 1_000_000;
 const MIXED_PAR_RUNS: usize = 1_000_000;
+const PRUNING_RUNS: usize = 10_000;
 
 
 fn main() {
@@ -33,10 +31,20 @@ fn main() {
 
     for (name, puzzles) in &puzzles {
         let name = format!("{:>width$}", name, width=longest_name);
-        for (i, (puzzle, solution)) in puzzles.iter().enumerate() {
+        for (i, (puzzle, column)) in puzzles.iter().enumerate() {
+            let solution = match column {
+                SolutionColumn::Given(solution) => solution,
+                SolutionColumn::NotUnique => {
+                    if has_unique_solution(puzzle.clone()) {
+                        cprintln!("<r, bold>\n{name} - {i} is marked as not unique, but has a unique solution");
+                    }
+                    continue;
+                }
+                SolutionColumn::Missing => continue,
+            };
+
             let s = solve_backtracking(puzzle.clone());
-            let solution = solution.clone();
-            if s != solution {
+            if s.as_ref() != Some(solution) {
                 cprintln!("<r, bold>\nFailed to solve {name} - {i}:");
                 if let Some(s) = s {
                     println!("Given solution:\n{s}")
@@ -45,18 +53,32 @@ fn main() {
                     println!("No solution found")
                 }
 
-                if let Some(solution) = solution {
-                    println!("Actual solution:\n{solution}")
-                }
-                else {
-                    println!("No actual solution")
-                }
+                println!("Actual solution:\n{solution}")
             }
         }
     }
 
     cprintln!("<g, bold>Done");
 
+    cprintln!("<b, bold>Rating puzzles...");
+
+    for (name, puzzles) in &puzzles {
+        let name = format!("{:<width$}", name, width=longest_name);
+        let mut grades = [0usize; Grade::ALL.len()];
+        let mut invalid = 0usize;
+        for (puzzle, _) in puzzles {
+            match rate(puzzle) {
+                Ok(rating) => grades[rating.grade() as usize] += 1,
+                Err(_) => invalid += 1,
+            }
+        }
+
+        let counts = Grade::ALL.iter()
+            .map(|g| format!("{}: {}", util::title_case(g.name()), grades[*g as usize]))
+            .join(" | ");
+        cprintln!("<g, bold>{name} $ {counts} | Invalid: {invalid}");
+    }
+
     cprintln!("<b, bold>Measuring performance...");
 
     for (name, puzzles) in &puzzles {
@@ -82,7 +104,7 @@ fn main() {
         );
     }
 
-    let a_puzzles = puzzles.iter().map(|(_, v)| v).flatten().map(|(p, _)| p.clone()).collect_vec();
+    let a_puzzles = puzzles.iter().flat_map(|(_, v)| v).map(|(p, _)| p.clone()).collect_vec();
     let total_solved = MIXED_RUNS * a_puzzles.len();
     cprint!("<b, bold>Timing mixed [{} runs | {} puzzles]", MIXED_RUNS.separate_with_commas(), total_solved.separate_with_commas());
     stdout().flush().ok();
@@ -104,6 +126,28 @@ fn main() {
         (1f64 / (duration / total_solved as u32).as_secs_f64()) as usize,
     );
 
+    cprintln!("<b, bold>{:-^101}", "Locked Candidates Pruning");
+
+    let total_solved = PRUNING_RUNS * a_puzzles.len();
+    cprint!("<b, bold>Timing mixed [{} runs | {} puzzles]", PRUNING_RUNS.separate_with_commas(), total_solved.separate_with_commas());
+    stdout().flush().ok();
+    let start = Instant::now();
+    for _ in 0..PRUNING_RUNS {
+        for puzzle in &a_puzzles {
+            let s = solve_backtracking_with(puzzle.clone(), Technique::LOCKED_CANDIDATES);
+            black_box(s);
+        }
+    }
+    let duration = start.elapsed();
+
+    cprintln!("\r<g, bold>{} $ Total solved: {} | Duration: {:?} | Time per: {:?} | Per second: {}",
+        all_text,
+        total_solved.separate_with_commas(),
+        duration,
+        duration / total_solved as u32,
+        (1f64 / (duration / total_solved as u32).as_secs_f64()) as usize,
+    );
+
     cprintln!("<b, bold>{:-^101}", "Parallel Tests");
 
     for (name, puzzles) in &puzzles {
@@ -130,7 +174,7 @@ fn main() {
         );
     }
 
-    let a_puzzles = puzzles.iter().map(|(_, v)| v).flatten().map(|(p, _)| p.clone()).collect_vec();
+    let a_puzzles = puzzles.iter().flat_map(|(_, v)| v).map(|(p, _)| p.clone()).collect_vec();
     let total_solved = MIXED_PAR_RUNS * a_puzzles.len();
     cprint!("<b, bold>Timing mixed [{} runs | {} puzzles]", MIXED_PAR_RUNS.separate_with_commas(), total_solved.separate_with_commas());
     stdout().flush().ok();
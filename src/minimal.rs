@@ -0,0 +1,123 @@
+use crate::solution::Solution;
+use crate::solver::has_unique_solution;
+use itertools::iproduct;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinimalityReport {
+    /// Whether the puzzle has exactly one solution
+    pub unique: bool,
+    /// Clues that can each be removed on their own without losing uniqueness.
+    ///
+    /// Removing one may make another necessary, so they can't always all be
+    /// removed at once.
+    pub redundant: Vec<(usize, usize)>,
+}
+
+impl MinimalityReport {
+    /// Whether every clue is needed for the solution to be unique
+    pub fn is_minimal(&self) -> bool {
+        self.unique && self.redundant.is_empty()
+    }
+}
+
+/// Checks which clues of a puzzle are needed for its solution to be unique
+pub fn check_minimality(puzzle: &Solution) -> MinimalityReport {
+    if !has_unique_solution(puzzle.clone()) {
+        return MinimalityReport {
+            unique: false,
+            redundant: Vec::new(),
+        };
+    }
+
+    let mut redundant = Vec::new();
+    let mut puzzle = puzzle.clone();
+    for (y, x) in iproduct!(0..9, 0..9) {
+        let val = puzzle.get(x, y);
+        if val == 9 {
+            continue;
+        }
+
+        puzzle.set(x, y, 9);
+        if has_unique_solution(puzzle.clone()) {
+            redundant.push((x, y));
+        }
+        puzzle.set(x, y, val);
+    }
+
+    MinimalityReport {
+        unique: true,
+        redundant,
+    }
+}
+
+/// Removes redundant clues, in reading order, until the puzzle is minimal.
+///
+/// Returns `None` if the puzzle doesn't have a unique solution.
+pub fn minimize(puzzle: &Solution) -> Option<Solution> {
+    let report = check_minimality(puzzle);
+    if !report.unique {
+        return None;
+    }
+
+    let mut puzzle = puzzle.clone();
+    for (x, y) in report.redundant {
+        let val = puzzle.get(x, y);
+        puzzle.set(x, y, 9);
+        if !has_unique_solution(puzzle.clone()) {
+            puzzle.set(x, y, val);
+        }
+    }
+
+    Some(puzzle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::Generator;
+    use crate::solver::solve_backtracking;
+
+    #[test]
+    fn generated_puzzles_are_minimal() {
+        let generated = Generator::new(5).generate();
+        assert!(check_minimality(&generated.puzzle).is_minimal());
+    }
+
+    #[test]
+    fn reports_and_strips_redundant_clues() {
+        let generated = Generator::new(5).generate();
+        let mut padded = generated.puzzle.clone();
+        let added = iproduct!(0..9, 0..9)
+            .map(|(y, x)| (x, y))
+            .filter(|&(x, y)| padded.get(x, y) == 9)
+            .step_by(7)
+            .take(4)
+            .collect::<Vec<_>>();
+        for &(x, y) in &added {
+            padded.set(x, y, generated.solution.get(x, y));
+        }
+
+        let report = check_minimality(&padded);
+        assert!(report.unique);
+        assert!(!report.is_minimal());
+        for cell in &added {
+            assert!(report.redundant.contains(cell), "{cell:?} should be redundant");
+        }
+
+        let minimal = minimize(&padded).unwrap();
+        assert!(check_minimality(&minimal).is_minimal());
+        assert_eq!(solve_backtracking(minimal.clone()), Some(generated.solution.clone()));
+        for (y, x) in iproduct!(0..9, 0..9) {
+            assert!(minimal.get(x, y) == 9 || minimal.get(x, y) == padded.get(x, y));
+        }
+    }
+
+    #[test]
+    fn puzzles_without_unique_solution_are_not_minimal() {
+        let one_clue = Solution::load_string(include_str!("../data/sudoku2.txt")).unwrap();
+        let report = check_minimality(&one_clue);
+        assert!(!report.unique);
+        assert!(!report.is_minimal());
+        assert_eq!(minimize(&one_clue), None);
+    }
+}
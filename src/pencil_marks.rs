@@ -0,0 +1,215 @@
+use crate::board::{Possibilities, SudokuPossibility};
+use crate::error::{LoadError, ParseError};
+use crate::logical::LogicalSolver;
+use crate::solution::Solution;
+use itertools::Itertools;
+
+/// Characters used to draw the borders of a pencil-mark grid
+const BORDER_CHARS: &[char] = &['.', '-', ':', '+', '\'', '|', '*', '='];
+
+impl Possibilities {
+    /// Parses a pencil-mark grid: 9 rows of 9 whitespace-separated candidate
+    /// strings, such as `4 1679 12679 | 139 2369 269 | 8 1239 5`.
+    ///
+    /// Box borders are ignored and `0` marks a cell with no candidates.
+    pub fn from_pencil_marks(contents: &str) -> Result<Self, ParseError> {
+        let mut possibilities = Possibilities::new_full();
+        let mut y = 0;
+
+        for (i, line) in contents.lines().enumerate() {
+            if !line.chars().any(|c| c.is_ascii_digit()) {
+                if let Some((column, found)) = line
+                    .chars()
+                    .enumerate()
+                    .find(|(_, c)| !c.is_whitespace() && !BORDER_CHARS.contains(c))
+                {
+                    return Err(ParseError::UnexpectedCharacter { line: i + 1, column: column + 1, found });
+                }
+                continue;
+            }
+            if y == 9 {
+                return Err(ParseError::TooManyLines { line: i + 1 });
+            }
+
+            let mut x = 0;
+            let mut cell = None;
+            for (column, c) in line.chars().chain([' ']).enumerate() {
+                match c {
+                    '1'..='9' => {
+                        let val = SudokuPossibility::new_val(c as u8 - b'1');
+                        cell = Some(cell.unwrap_or(SudokuPossibility::new_empty()).or(val));
+                    }
+                    '0' => cell = Some(cell.unwrap_or(SudokuPossibility::new_empty())),
+                    c if c.is_whitespace() || c == '|' => {
+                        if let Some(cell) = cell.take() {
+                            if x < 9 {
+                                possibilities.set(x, y, cell);
+                            }
+                            x += 1;
+                        }
+                    }
+                    found => {
+                        return Err(ParseError::UnexpectedCharacter { line: i + 1, column: column + 1, found });
+                    }
+                }
+            }
+
+            if x != 9 {
+                return Err(ParseError::WrongCellCount { line: i + 1, found: x });
+            }
+            y += 1;
+        }
+
+        if y != 9 {
+            return Err(ParseError::TooFewRows { found: y });
+        }
+        Ok(possibilities)
+    }
+
+    /// Writes the candidates of every open cell as a pencil-mark grid that
+    /// [`Possibilities::from_pencil_marks`] reads back, with cells filled in
+    /// `solution` as their digit
+    pub fn to_pencil_marks(&self, solution: &Solution) -> String {
+        pencil_marks(|x, y| match solution.get(x, y) {
+            9 => self.get(x, y),
+            val => SudokuPossibility::new_val(val),
+        })
+    }
+}
+
+impl LogicalSolver {
+    /// Resumes solving from a pencil-mark grid.
+    ///
+    /// Cells with a single candidate are taken as filled, as pencil-mark
+    /// exports don't distinguish them from naked singles.
+    pub fn from_pencil_marks(contents: &str) -> Result<Self, LoadError> {
+        let possibilities = Possibilities::from_pencil_marks(contents)?;
+        LogicalSolver::from_possibilities(possibilities).map_err(LoadError::Invalid)
+    }
+
+    /// Writes the current state as a pencil-mark grid, with filled cells as
+    /// their digit
+    pub fn to_pencil_marks(&self) -> String {
+        self.possibilities().to_pencil_marks(self.solution())
+    }
+}
+
+fn pencil_marks<F: Fn(usize, usize) -> SudokuPossibility>(cell: F) -> String {
+    let strings = (0..9)
+        .map(|y| {
+            (0..9)
+                .map(|x| {
+                    let cell = cell(x, y);
+                    if cell.is_empty() {
+                        "0".to_string()
+                    } else {
+                        (0..9).filter(|n| cell.has(*n)).map(|n| n + 1).join("")
+                    }
+                })
+                .collect_vec()
+        })
+        .collect_vec();
+
+    let widths = (0..9)
+        .map(|x| strings.iter().map(|row| row[x].len()).max().unwrap())
+        .collect_vec();
+    let stack_widths = widths.chunks(3).map(|w| w.iter().sum::<usize>() + 4).collect_vec();
+    let border = |left: char, middle: char, right: char| {
+        let dashes = stack_widths.iter().map(|w| "-".repeat(*w)).join(&middle.to_string());
+        format!("{left}{dashes}{right}\n")
+    };
+
+    let mut out = border('.', '.', '.');
+    for (y, row) in strings.iter().enumerate() {
+        out.push('|');
+        for (x, s) in row.iter().enumerate() {
+            out += &format!(" {s:<width$}", width = widths[x]);
+            if (x + 1) % 3 == 0 {
+                out += " |";
+            }
+        }
+        out.push('\n');
+
+        if y == 2 || y == 5 {
+            out += &border(':', '+', ':');
+        }
+    }
+    out += &border('\'', '\'', '\'');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::iproduct;
+
+    const PUZZLE: &str = ".2...69......5..2.6..3.....94...7......4..7...3.2...8...9.4....3..9.2.17..8.....2";
+
+    #[test]
+    fn candidates_round_trip() {
+        let mut possibilities = Possibilities::new_full();
+        possibilities.remove(0, 0, 4);
+        possibilities.remove(8, 8, 0);
+        possibilities.set(4, 4, SudokuPossibility::new_empty());
+
+        let marks = possibilities.to_pencil_marks(&Solution::new_empty());
+        let parsed = Possibilities::from_pencil_marks(&marks).unwrap();
+        for (y, x) in iproduct!(0..9, 0..9) {
+            assert_eq!(parsed.get(x, y), possibilities.get(x, y), "r{}c{}", y + 1, x + 1);
+        }
+    }
+
+    #[test]
+    fn export_keeps_givens() {
+        let puzzle = Solution::from_line(PUZZLE).unwrap();
+        let marks = Possibilities::from_solution(&puzzle).to_pencil_marks(&puzzle);
+
+        let resumed = LogicalSolver::from_pencil_marks(&marks).unwrap();
+        assert_eq!(resumed.solution(), &puzzle);
+        assert_eq!(resumed.to_pencil_marks(), marks);
+        assert_eq!(
+            resumed.solve().solution,
+            LogicalSolver::new(&puzzle).unwrap().solve().solution
+        );
+    }
+
+    #[test]
+    fn resumes_mid_solve() {
+        let puzzle = Solution::from_line(PUZZLE).unwrap();
+        let mut solver = LogicalSolver::new(&puzzle).unwrap();
+        for _ in 0..10 {
+            solver.step();
+        }
+
+        let resumed = LogicalSolver::from_pencil_marks(&solver.to_pencil_marks()).unwrap();
+        assert_eq!(resumed.solution(), solver.solution());
+        assert_eq!(resumed.solve().solution, solver.solve().solution);
+    }
+
+    #[test]
+    fn errors_report_their_position() {
+        let row = "1 2 3 | 4 5 6 | 7 8 9\n";
+        assert_eq!(
+            Possibilities::from_pencil_marks(&format!("{row}1 2 x | 4 5 6 | 7 8 9\n")).err(),
+            Some(ParseError::UnexpectedCharacter { line: 2, column: 5, found: 'x' })
+        );
+        assert_eq!(
+            Possibilities::from_pencil_marks(&format!("{row}1 2 3 | 4 5 6 | 7 8\n")).err(),
+            Some(ParseError::WrongCellCount { line: 2, found: 8 })
+        );
+        assert_eq!(
+            Possibilities::from_pencil_marks(&row.repeat(3)).err(),
+            Some(ParseError::TooFewRows { found: 3 })
+        );
+    }
+
+    #[test]
+    fn clashing_singles_are_invalid() {
+        let mut possibilities = Possibilities::new_full();
+        possibilities.set(0, 0, SudokuPossibility::new_val(4));
+        possibilities.set(5, 0, SudokuPossibility::new_val(4));
+
+        let marks = possibilities.to_pencil_marks(&Solution::new_empty());
+        assert!(matches!(LogicalSolver::from_pencil_marks(&marks), Err(LoadError::Invalid(_))));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::logical::LogicalSolver;
+use crate::solution::Solution;
+use crate::techniques::Technique;
+use crate::validation::ValidationReport;
+use std::fmt::{Display, Formatter};
+
+/// Difficulty band, named after the puzzle sets in `converted/`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Grade {
+    VeryEasy,
+    Easy,
+    Medium,
+    Hard,
+    /// Can't be solved with the implemented techniques
+    Beyond,
+}
+
+impl Grade {
+    pub const ALL: &'static [Grade] = &[
+        Grade::VeryEasy,
+        Grade::Easy,
+        Grade::Medium,
+        Grade::Hard,
+        Grade::Beyond,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Grade::VeryEasy => "very_easy",
+            Grade::Easy => "easy",
+            Grade::Medium => "medium",
+            Grade::Hard => "hard",
+            Grade::Beyond => "beyond",
+        }
+    }
+
+    /// Grade of a puzzle whose hardest required technique has `difficulty`
+    pub fn from_difficulty(difficulty: f32) -> Self {
+        if difficulty <= 1.0 {
+            Grade::VeryEasy
+        } else if difficulty <= 1.5 {
+            Grade::Easy
+        } else if difficulty <= 3.0 {
+            Grade::Medium
+        } else {
+            Grade::Hard
+        }
+    }
+}
+
+impl Display for Grade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rating {
+    /// Difficulty of the hardest technique used
+    pub difficulty: f32,
+    /// Sum of the scores of every step taken
+    pub score: u32,
+    pub hardest: Option<Technique>,
+    /// How many times each technique was used, in difficulty order
+    pub counts: Vec<(Technique, usize)>,
+    /// Whether the techniques were enough to solve the puzzle
+    pub solved: bool,
+}
+
+impl Rating {
+    pub fn steps(&self) -> usize {
+        self.counts.iter().map(|(_, c)| c).sum()
+    }
+
+    pub fn grade(&self) -> Grade {
+        if !self.solved {
+            Grade::Beyond
+        } else {
+            Grade::from_difficulty(self.difficulty)
+        }
+    }
+}
+
+impl Display for Rating {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.1} ({}) | Score: {}", self.difficulty, self.grade(), self.score)?;
+        for (technique, count) in &self.counts {
+            write!(f, " | {technique}: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Rates a puzzle by solving it with logical techniques only.
+///
+/// Techniques are always tried simplest first, so the hardest technique used
+/// is the hardest one required.
+pub fn rate(puzzle: &Solution) -> Result<Rating, ValidationReport> {
+    Ok(rate_with(LogicalSolver::new(puzzle)?))
+}
+
+/// Rates a puzzle with a pre-configured solver
+pub fn rate_with(solver: LogicalSolver) -> Rating {
+    let path = solver.solve();
+
+    let mut counts: Vec<(Technique, usize)> = Vec::new();
+    for step in &path.steps {
+        match counts.iter_mut().find(|(t, _)| *t == step.technique) {
+            Some((_, c)) => *c += 1,
+            None => counts.push((step.technique, 1)),
+        }
+    }
+    counts.sort_by_key(|(t, _)| *t);
+
+    let hardest = counts.last().map(|(t, _)| *t);
+    Rating {
+        difficulty: hardest.map_or(0.0, Technique::difficulty),
+        score: counts.iter().map(|(t, c)| t.score() * *c as u32).sum(),
+        hardest,
+        counts,
+        solved: path.solved(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(line: &str) -> Solution {
+        line.parse().unwrap()
+    }
+
+    #[test]
+    fn singles_only_puzzle_is_very_easy() {
+        let rating = rate(&grid("1.43829562.54671383869514.24615238977381496259528763145296347816.7298543843.15269")).unwrap();
+
+        assert!(rating.solved);
+        assert_eq!(rating.grade(), Grade::VeryEasy);
+        assert_eq!(rating.hardest, Some(Technique::NakedSingle));
+        assert_eq!(rating.difficulty, Technique::NakedSingle.difficulty());
+        assert_eq!(rating.steps(), 5);
+        assert_eq!(rating.score, 5 * Technique::NakedSingle.score());
+    }
+
+    #[test]
+    fn rating_follows_hardest_technique() {
+        let rating = rate(&grid("9.7...6511.5.9..8...........5.4......2.....4...1..6.....86...3...41..9....9.5..6.")).unwrap();
+
+        assert!(rating.solved);
+        assert_eq!(rating.grade(), Grade::Medium);
+        assert_eq!(rating.hardest, Some(Technique::LockedPointing));
+        assert_eq!(rating.difficulty, Technique::LockedPointing.difficulty());
+        assert!(rating.counts.windows(2).all(|w| w[0].0 < w[1].0));
+        let score: u32 = rating.counts.iter().map(|(t, c)| t.score() * *c as u32).sum();
+        assert_eq!(rating.score, score);
+    }
+
+    #[test]
+    fn unsolved_puzzle_is_beyond() {
+        let puzzle = grid(".......5.2.7..9...6..351...5......1...3.....8...82.53.....7.8.4..62......8....7..");
+        let solver = LogicalSolver::new(&puzzle).unwrap().with_techniques(&[Technique::NakedSingle]);
+
+        let rating = rate_with(solver);
+        assert!(!rating.solved);
+        assert_eq!(rating.grade(), Grade::Beyond);
+    }
+
+    #[test]
+    fn grades_from_difficulty() {
+        assert_eq!(Grade::from_difficulty(1.0), Grade::VeryEasy);
+        assert_eq!(Grade::from_difficulty(1.5), Grade::Easy);
+        assert_eq!(Grade::from_difficulty(2.6), Grade::Medium);
+        assert_eq!(Grade::from_difficulty(3.0), Grade::Medium);
+        assert_eq!(Grade::from_difficulty(3.2), Grade::Hard);
+    }
+}
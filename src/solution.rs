@@ -1,35 +1,55 @@
 use crate::board::Possibilities;
+use crate::error::{LoadError, ParseError};
+use crate::validation::{Duplicate, Unit, ValidationReport};
 use arrayvec::ArrayVec;
 use std::fmt::{Display, Formatter};
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::path::Path;
+use std::str::FromStr;
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Solution {
     inner: [[u8; 9]; 9],
     remaining: u8,
 }
 
 impl Solution {
-    #[allow(dead_code)]
-    pub fn load<P: AsRef<Path>>(path: P) -> Self {
-        let contents = fs::read_to_string(path).expect("File read error");
-        Self::load_string(contents)
+    pub fn new_empty() -> Self {
+        Solution {
+            inner: [[9; 9]; 9],
+            remaining: 81,
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        let contents = fs::read_to_string(path)?;
+        Ok(Self::load_string(&contents)?)
     }
 
-    pub fn load_string(contents: String) -> Self {
+    /// Parses either a 9-line grid or a single-line 81-character puzzle
+    pub fn load_string(contents: &str) -> Result<Self, ParseError> {
+        let mut lines = contents.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+        if let (Some((i, line)), None) = (lines.next(), lines.next()) {
+            let trimmed = line.trim_start();
+            if trimmed.chars().count() >= 81 {
+                let indent = line.chars().count() - trimmed.chars().count();
+                return Self::from_line(trimmed).map_err(|e| e.after_column(indent).at_line(i + 1));
+            }
+        }
+
         let mut board = [[9; 9]; 9];
         let mut remaining = 81;
 
         for (i, line) in contents.lines().enumerate() {
             if i > 8 && !line.trim().is_empty() {
-                panic!("Too many lines");
+                return Err(ParseError::TooManyLines { line: i + 1 });
             } else if i > 8 {
                 continue;
             }
             for (j, c) in line.chars().enumerate() {
-                if j > 8 && !c.to_string().is_empty() {
-                    panic!("Line too long in input");
+                if j > 8 && !c.is_whitespace() {
+                    return Err(ParseError::LineTooLong { line: i + 1, column: j + 1 });
                 } else if j > 8 {
                     continue;
                 }
@@ -41,16 +61,77 @@ impl Solution {
                         board[i][j] = n as u8 - 1;
                         remaining -= 1;
                     } else {
-                        panic!("Expected number, found {c}")
+                        return Err(ParseError::UnexpectedCharacter {
+                            line: i + 1,
+                            column: j + 1,
+                            found: c,
+                        });
                     }
                 }
             }
         }
 
-        Solution {
+        Ok(Solution {
             inner: board,
             remaining,
+        })
+    }
+
+    /// Parses a single-line puzzle, ignoring any trailing comment
+    pub fn from_line(line: &str) -> Result<Self, ParseError> {
+        Self::parse_line(line).map(|(puzzle, _)| puzzle)
+    }
+
+    /// Parses a single-line puzzle of 81 cells in reading order, with `.`,
+    /// `0` or `_` for empty cells.
+    ///
+    /// Anything after the cells must be separated by whitespace, `#` or `;`
+    /// and is returned, trimmed, as a comment.
+    pub fn parse_line(line: &str) -> Result<(Self, Option<&str>), ParseError> {
+        let mut solution = Solution::new_empty();
+
+        let mut chars = line.char_indices();
+        for i in 0..81 {
+            let Some((_, c)) = chars.next() else {
+                return Err(ParseError::TooFewCells { line: 1, found: i });
+            };
+            match c {
+                '.' | '0' | '_' => {}
+                '1'..='9' => solution.set(i % 9, i / 9, c as u8 - b'1'),
+                _ => return Err(ParseError::UnexpectedCharacter { line: 1, column: i + 1, found: c }),
+            }
+        }
+
+        let rest = chars.as_str();
+        match rest.chars().next() {
+            None => return Ok((solution, None)),
+            Some(c) if c.is_whitespace() || c == '#' || c == ';' => {}
+            Some(c) => return Err(ParseError::UnexpectedCharacter { line: 1, column: 82, found: c }),
         }
+
+        let comment = rest.trim();
+        Ok((solution, (!comment.is_empty()).then_some(comment)))
+    }
+
+    /// Writes the puzzle as 81 characters in reading order, with `.` for
+    /// empty cells
+    pub fn to_line(&self) -> String {
+        let mut line = String::with_capacity(81);
+        for row in &self.inner {
+            for &cell in row {
+                line.push(if cell == 9 { '.' } else { (b'1' + cell) as char });
+            }
+        }
+        line
+    }
+
+    /// Writes the puzzle as a single line followed by `comment`
+    pub fn to_line_with_comment(&self, comment: &str) -> String {
+        let comment = comment.trim();
+        if comment.is_empty() {
+            return self.to_line();
+        }
+        format!("{} {}", self.to_line(), comment)
     }
 
     pub fn is_valid(&self) -> bool {
@@ -70,6 +151,35 @@ impl Solution {
         true
     }
 
+    /// Lists every digit given more than once in a row, column or box.
+    ///
+    /// Slower than [`Solution::is_valid`], but reports all conflicts rather
+    /// than stopping at the first.
+    pub fn validate(&self) -> ValidationReport {
+        let mut duplicates = Vec::new();
+
+        for unit in Unit::all() {
+            let cells = unit.cells();
+            for digit in 0..9 {
+                let found = cells
+                    .iter()
+                    .copied()
+                    .filter(|&(x, y)| self.inner[y][x] == digit)
+                    .collect::<Vec<_>>();
+                if found.len() > 1 {
+                    duplicates.push(Duplicate { digit, unit, cells: found });
+                }
+            }
+        }
+
+        ValidationReport { duplicates }
+    }
+
+    /// Every given that clashes with another given in its row, column or box
+    pub fn conflicting_cells(&self) -> Vec<(usize, usize)> {
+        self.validate().conflicting_cells()
+    }
+
     pub fn undo<const C: usize>(&mut self, revert: &mut ArrayVec<(u8, u8), C>, prev_size: usize) {
         for _ in prev_size..revert.len() {
             let (x, y) = revert.pop().unwrap();
@@ -89,6 +199,12 @@ impl Solution {
         self.inner[y][x] = val;
     }
 
+    /// Number of filled cells
+    #[inline]
+    pub fn clue_count(&self) -> usize {
+        81 - self.remaining as usize
+    }
+
     #[inline]
     pub fn solved(&self) -> bool {
         self.remaining == 0
@@ -100,6 +216,14 @@ impl Solution {
     }
 }
 
+impl FromStr for Solution {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::load_string(s)
+    }
+}
+
 impl PartialEq for Solution {
     fn eq(&self, other: &Self) -> bool {
         #[cfg(not(debug_assertions))]
@@ -115,6 +239,14 @@ impl PartialEq for Solution {
     }
 }
 
+impl Eq for Solution {}
+
+impl Hash for Solution {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
 impl Display for Solution {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "┌───────┬───────┬───────┐")?;
@@ -145,3 +277,76 @@ impl Display for Solution {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_line_errors_report_their_line() {
+        let contents = format!("\n{}x", ".".repeat(80));
+        assert_eq!(
+            Solution::load_string(&contents),
+            Err(ParseError::UnexpectedCharacter { line: 2, column: 81, found: 'x' })
+        );
+    }
+
+    #[test]
+    fn single_line_round_trips() {
+        let line = "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......";
+        let puzzle = Solution::load_string(line).unwrap();
+        assert_eq!(puzzle.to_line(), line);
+        assert_eq!(puzzle.clue_count(), 17);
+    }
+
+    #[test]
+    fn single_line_ignores_indentation() {
+        let line = "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......";
+        let puzzle: Solution = format!("  {line}\n").parse().unwrap();
+        assert_eq!(puzzle.to_line(), line);
+
+        let contents = format!("\n\t {}x", ".".repeat(80));
+        assert_eq!(
+            Solution::load_string(&contents),
+            Err(ParseError::UnexpectedCharacter { line: 2, column: 83, found: 'x' })
+        );
+    }
+
+    #[test]
+    fn blank_comment_is_left_out() {
+        let puzzle = Solution::new_empty();
+        assert_eq!(puzzle.to_line_with_comment(""), puzzle.to_line());
+        assert_eq!(puzzle.to_line_with_comment("  "), puzzle.to_line());
+        assert_eq!(puzzle.to_line_with_comment(" hard "), format!("{} hard", puzzle.to_line()));
+    }
+
+    #[test]
+    fn validate_reports_every_duplicate() {
+        let mut grid = Solution::new_empty();
+        for (x, y) in [(0, 0), (5, 0), (1, 1), (0, 6)] {
+            grid.set(x, y, 3);
+        }
+        grid.set(8, 8, 3);
+
+        let report = grid.validate();
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.duplicates,
+            [
+                Duplicate { digit: 3, unit: Unit::Row(0), cells: vec![(0, 0), (5, 0)] },
+                Duplicate { digit: 3, unit: Unit::Column(0), cells: vec![(0, 0), (0, 6)] },
+                Duplicate { digit: 3, unit: Unit::Box(0), cells: vec![(0, 0), (1, 1)] },
+            ]
+        );
+        assert_eq!(grid.conflicting_cells(), [(0, 0), (5, 0), (1, 1), (0, 6)]);
+        assert!(!grid.is_valid());
+    }
+
+    #[test]
+    fn valid_grid_has_no_duplicates() {
+        let grid = Solution::load_string(include_str!("../data/sudoku2.txt")).unwrap();
+        assert!(grid.validate().is_valid());
+        assert!(grid.conflicting_cells().is_empty());
+        assert!(grid.is_valid());
+    }
+}
@@ -0,0 +1,155 @@
+use crate::board::Possibilities;
+use crate::solution::Solution;
+use crate::solver::{propagate, Propagation};
+use arrayvec::ArrayVec;
+
+/// Lazily enumerates every solution of a puzzle.
+///
+/// The search only advances as far as the next solution each time `next` is
+/// called, so dropping the iterator early stops the search.
+pub struct Solutions {
+    solution: Solution,
+    to_revert_stack: ArrayVec<(u8, u8), { 81 * 81 }>,
+    frames: Vec<Frame>,
+    start: Option<Possibilities>,
+    // Revert stack length to undo to before continuing past a found solution
+    pending_undo: Option<usize>,
+}
+
+struct Frame {
+    possibilities: Possibilities,
+    x: usize,
+    y: usize,
+    next: u8,
+    prev_to_revert_stack: usize,
+}
+
+impl Solutions {
+    pub fn new(puzzle: Solution) -> Self {
+        let start = if puzzle.is_valid() {
+            Some(Possibilities::from_solution(&puzzle))
+        } else {
+            None
+        };
+
+        Solutions {
+            solution: puzzle,
+            to_revert_stack: ArrayVec::new(),
+            frames: Vec::new(),
+            start,
+            pending_undo: None,
+        }
+    }
+
+    /// Propagates from `possibilities`, returning whether a solution was reached
+    fn expand(&mut self, mut possibilities: Possibilities) -> bool {
+        let prev_to_revert_stack = self.to_revert_stack.len();
+
+        match propagate(&mut possibilities, &mut self.solution, &mut self.to_revert_stack) {
+            Propagation::Contradiction => {
+                self.solution.undo(&mut self.to_revert_stack, prev_to_revert_stack);
+                false
+            }
+            Propagation::Solved => {
+                self.pending_undo = Some(prev_to_revert_stack);
+                true
+            }
+            Propagation::Branch(x, y) => {
+                self.to_revert_stack.push((x as u8, y as u8));
+                self.frames.push(Frame {
+                    possibilities,
+                    x,
+                    y,
+                    next: 0,
+                    prev_to_revert_stack,
+                });
+                false
+            }
+        }
+    }
+}
+
+impl Iterator for Solutions {
+    type Item = Solution;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(prev) = self.pending_undo.take() {
+            self.solution.undo(&mut self.to_revert_stack, prev);
+        }
+
+        if let Some(possibilities) = self.start.take() {
+            if self.expand(possibilities) {
+                return Some(self.solution.clone());
+            }
+        }
+
+        while let Some(frame) = self.frames.last_mut() {
+            let cell_possibilities = frame.possibilities.get(frame.x, frame.y);
+            let Some(n) = (frame.next..9).find(|n| cell_possibilities.has(*n)) else {
+                let prev = frame.prev_to_revert_stack;
+                self.frames.pop();
+                self.solution.undo(&mut self.to_revert_stack, prev);
+                continue;
+            };
+            frame.next = n + 1;
+
+            let (x, y) = (frame.x, frame.y);
+            self.solution.set(x, y, n);
+
+            let mut new_possibilities = frame.possibilities.clone();
+            new_possibilities.update_found(x, y, n);
+
+            if self.expand(new_possibilities) {
+                return Some(self.solution.clone());
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::Solution;
+    use crate::solver::{count_solutions, solutions};
+    use itertools::{iproduct, Itertools};
+
+    /// A solved grid with its first `rows` rows emptied
+    fn without_rows(rows: usize) -> Solution {
+        let mut puzzle: Solution =
+            "425816973893754621617329548941687235582431769736295184279148356354962817168573492".parse().unwrap();
+        for (y, x) in iproduct!(0..rows, 0..9) {
+            puzzle.set(x, y, 9);
+        }
+        puzzle
+    }
+
+    #[test]
+    fn yields_distinct_solved_grids() {
+        let one_clue = Solution::load_string(include_str!("../data/sudoku2.txt")).unwrap();
+        let found = solutions(one_clue.clone()).take(50).collect_vec();
+
+        assert_eq!(found.len(), 50);
+        assert_eq!(found.iter().unique().count(), 50);
+        for solution in &found {
+            assert!(solution.solved() && solution.is_valid());
+            assert_eq!(solution.get(0, 8), one_clue.get(0, 8));
+        }
+    }
+
+    #[test]
+    fn count_matches_count_solutions() {
+        for rows in [0, 2, 3] {
+            let puzzle = without_rows(rows);
+            assert_eq!(solutions(puzzle.clone()).count(), count_solutions(puzzle, usize::MAX));
+        }
+    }
+
+    #[test]
+    fn invalid_grid_has_no_solutions() {
+        let mut invalid = without_rows(1);
+        invalid.set(0, 0, 3);
+        invalid.set(1, 0, 3);
+        assert_eq!(solutions(invalid).next(), None);
+    }
+}
@@ -1,6 +1,8 @@
 use arrayvec::ArrayVec;
 use crate::board::Possibilities;
 use crate::solution::Solution;
+use crate::solutions::Solutions;
+use crate::techniques::Technique;
 use itertools::iproduct;
 
 pub fn solve_backtracking(mut solution: Solution) -> Option<Solution> {
@@ -20,15 +22,189 @@ pub fn solve_backtracking(mut solution: Solution) -> Option<Solution> {
     }
 }
 
-pub fn recursively_attempt<const C: usize>(mut possibilities: Possibilities, solution: &mut Solution, to_revert_stack: &mut ArrayVec<(u8, u8), C>) {
+/// Like [`solve_backtracking`], additionally applying the elimination
+/// techniques in `pruning` at every step of the search.
+///
+/// Techniques that only place digits are ignored, since singles are always
+/// applied.
+pub fn solve_backtracking_with(mut solution: Solution, pruning: &[Technique]) -> Option<Solution> {
+    if !solution.is_valid() {
+        return None;
+    }
+
+    let possibilities = Possibilities::from_solution(&solution);
+    let mut to_revert_stack: ArrayVec<(u8, u8), {81 * 81}> = ArrayVec::new();
+
+    attempt(possibilities, &mut solution, &mut to_revert_stack, pruning, &mut |_| true);
     if solution.solved() {
-        return;
+        Some(solution)
+    } else {
+        None
     }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveOutcome {
+    /// The puzzle has exactly one solution
+    Solved(Solution),
+    /// The puzzle has more than one solution, the first two found are given
+    MultipleSolutions(Solution, Solution),
+    /// Givens clash with each other in a row, column or box
+    InvalidGivens { conflicts: Vec<(usize, usize)> },
+    /// Givens don't clash, but filling in forced digits reaches a dead end
+    Contradiction,
+    /// Givens are consistent, but the search found no solution
+    Unsolvable,
+}
 
+/// Solves `puzzle`, reporting why it was rejected if it has no unique solution.
+pub fn solve(puzzle: Solution) -> SolveOutcome {
+    let conflicts = puzzle.conflicting_cells();
+    if !conflicts.is_empty() {
+        return SolveOutcome::InvalidGivens { conflicts };
+    }
+
+    let mut possibilities = Possibilities::from_solution(&puzzle);
+    let mut solution = puzzle.clone();
+    let mut to_revert_stack: ArrayVec<(u8, u8), 81> = ArrayVec::new();
+    if let Propagation::Contradiction = propagate(&mut possibilities, &mut solution, &mut to_revert_stack) {
+        return SolveOutcome::Contradiction;
+    }
+
+    let mut solutions = solutions(puzzle);
+    match (solutions.next(), solutions.next()) {
+        (None, _) => SolveOutcome::Unsolvable,
+        (Some(first), None) => SolveOutcome::Solved(first),
+        (Some(first), Some(second)) => SolveOutcome::MultipleSolutions(first, second),
+    }
+}
+
+/// Counts the solutions of `puzzle`, stopping once `limit` have been found.
+///
+/// A `limit` of 2 is enough to tell apart puzzles with no solution, a unique
+/// solution and multiple solutions.
+pub fn count_solutions(mut puzzle: Solution, limit: usize) -> usize {
+    if limit == 0 || !puzzle.is_valid() {
+        return 0;
+    }
+
+    let possibilities = Possibilities::from_solution(&puzzle);
+    let mut to_revert_stack: ArrayVec<(u8, u8), {81 * 81}> = ArrayVec::new();
+
+    let mut count = 0;
+    attempt(possibilities, &mut puzzle, &mut to_revert_stack, &[], &mut |_| {
+        count += 1;
+        count >= limit
+    });
+    count
+}
+
+/// Lazily iterates over every solution of `puzzle`.
+pub fn solutions(puzzle: Solution) -> Solutions {
+    Solutions::new(puzzle)
+}
+
+pub fn has_unique_solution(puzzle: Solution) -> bool {
+    count_solutions(puzzle, 2) == 1
+}
+
+pub fn recursively_attempt<const C: usize>(possibilities: Possibilities, solution: &mut Solution, to_revert_stack: &mut ArrayVec<(u8, u8), C>) {
+    attempt(possibilities, solution, to_revert_stack, &[], &mut |_| true);
+}
+
+/// Searches for solutions, calling `on_solution` for each one found, using
+/// the techniques in `pruning` to eliminate candidates before guessing.
+///
+/// Returns `true`, leaving `solution` filled, as soon as `on_solution` does.
+/// Otherwise every change made is reverted and `false` is returned once the
+/// search space is exhausted.
+pub(crate) fn attempt<const C: usize, F: FnMut(&Solution) -> bool>(
+    mut possibilities: Possibilities,
+    solution: &mut Solution,
+    to_revert_stack: &mut ArrayVec<(u8, u8), C>,
+    pruning: &[Technique],
+    on_solution: &mut F,
+) -> bool {
     // let mut to_revert: Vec<(u8, u8)> = Vec::with_capacity(16);
     // let mut to_revert: ArrayVec<(u8, u8), 81> = ArrayVec::new(); // 8.9
     let prev_to_revert_stack = to_revert_stack.len();
 
+    let (x, y) = loop {
+        match propagate(&mut possibilities, solution, to_revert_stack) {
+            Propagation::Contradiction => {
+                solution.undo(to_revert_stack, prev_to_revert_stack);
+                return false;
+            }
+            Propagation::Solved => {
+                if on_solution(solution) {
+                    return true;
+                }
+                solution.undo(to_revert_stack, prev_to_revert_stack);
+                return false;
+            }
+            Propagation::Branch(x, y) => {
+                if !prune(&mut possibilities, solution, pruning) {
+                    break (x, y);
+                }
+            }
+        }
+    };
+
+    to_revert_stack.push((x as u8, y as u8));
+    let cell_possibilites = possibilities.get(x, y);
+    for n in 0..9 {
+        if !cell_possibilites.has(n) {
+            continue;
+        }
+
+        solution.set(x, y, n);
+
+        let mut new_possibilites = possibilities.clone();
+        new_possibilites.update_found(x, y, n);
+
+        if attempt(new_possibilites, solution, to_revert_stack, pruning, on_solution) {
+            return true;
+        }
+    }
+
+    solution.undo(to_revert_stack, prev_to_revert_stack);
+    false
+}
+
+/// Applies every elimination the techniques in `pruning` can find, returning
+/// whether any candidate was removed
+pub(crate) fn prune(possibilities: &mut Possibilities, solution: &Solution, pruning: &[Technique]) -> bool {
+    let mut changed = false;
+    for technique in pruning {
+        while let Some(step) = technique.find(solution, possibilities) {
+            if step.eliminations.is_empty() {
+                break;
+            }
+            for c in &step.eliminations {
+                possibilities.remove(c.x, c.y, c.digit);
+            }
+            changed = true;
+        }
+    }
+    changed
+}
+
+pub(crate) enum Propagation {
+    Solved,
+    Contradiction,
+    /// No more singles, guess at the cell with the fewest possibilities
+    Branch(usize, usize),
+}
+
+/// Places naked and hidden singles until none remain.
+///
+/// Every cell set is pushed onto `to_revert_stack`. Nothing is reverted on a
+/// contradiction; that is left to the caller.
+pub(crate) fn propagate<const C: usize>(
+    possibilities: &mut Possibilities,
+    solution: &mut Solution,
+    to_revert_stack: &mut ArrayVec<(u8, u8), C>,
+) -> Propagation {
     let mut change = true;
     let mut first_pass = true;
 
@@ -41,7 +217,7 @@ pub fn recursively_attempt<const C: usize>(mut possibilities: Possibilities, sol
 
     while change {
         if solution.solved() {
-            return;
+            return Propagation::Solved;
         }
 
         change = false;
@@ -66,8 +242,7 @@ pub fn recursively_attempt<const C: usize>(mut possibilities: Possibilities, sol
 
                 if count == 0 {
                     // println!("A");
-                    solution.undo(to_revert_stack, prev_to_revert_stack);
-                    return;
+                    return Propagation::Contradiction;
                 }
 
                 if count == 1 {
@@ -105,8 +280,7 @@ pub fn recursively_attempt<const C: usize>(mut possibilities: Possibilities, sol
                 for (n, x) in singles_data.iter().enumerate() {
                     if *x <= 8 {
                         if solution.get(*x as usize, y) != 9 {
-                            solution.undo(to_revert_stack, prev_to_revert_stack);
-                            return;
+                            return Propagation::Contradiction;
                         }
 
                         solution.set(*x as usize, y, n as u8);
@@ -152,8 +326,7 @@ pub fn recursively_attempt<const C: usize>(mut possibilities: Possibilities, sol
             for (n, y) in singles_data.iter().enumerate() {
                 if *y <= 8 {
                     if solution.get(x, *y as usize) != 9 {
-                        solution.undo(to_revert_stack, prev_to_revert_stack);
-                        return;
+                        return Propagation::Contradiction;
                     }
 
                     solution.set(x, *y as usize, n as u8);
@@ -197,8 +370,7 @@ pub fn recursively_attempt<const C: usize>(mut possibilities: Possibilities, sol
                     );
 
                     if solution.get(x, y) != 9 {
-                        solution.undo(to_revert_stack, prev_to_revert_stack);
-                        return;
+                        return Propagation::Contradiction;
                     }
 
                     solution.set(x, y, n as u8);
@@ -211,31 +383,105 @@ pub fn recursively_attempt<const C: usize>(mut possibilities: Possibilities, sol
     }
 
     if solution.solved() {
-        return;
+        return Propagation::Solved;
     }
 
     let (x, y) = lowest_pos.unwrap();
-    // println!("{x} {y}");
-    // return;
+    Propagation::Branch(x, y)
+}
 
-    to_revert_stack.push((x as u8, y as u8));
-    let cell_possibilites = possibilities.get(x, y);
-    for n in 0..9 {
-        if !cell_possibilites.has(n) {
-            continue;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNIQUE: &str = ".2...69......5..2.6..3.....94...7......4..7...3.2...8...9.4....3..9.2.17..8.....2";
+    const SOLVED: &str = "425816973893754621617329548941687235582431769736295184279148356354962817168573492";
+
+    fn puzzle(line: &str) -> Solution {
+        line.parse().unwrap()
+    }
+
+    /// [`SOLVED`] with its first `rows` rows emptied
+    fn without_rows(rows: usize) -> Solution {
+        let mut puzzle = puzzle(SOLVED);
+        for (y, x) in iproduct!(0..rows, 0..9) {
+            puzzle.set(x, y, 9);
         }
+        puzzle
+    }
 
-        solution.set(x, y, n);
+    #[test]
+    fn count_stops_at_limit() {
+        let one_clue = Solution::load_string(include_str!("../data/sudoku2.txt")).unwrap();
+        for limit in [1, 2, 5, 20] {
+            assert_eq!(count_solutions(one_clue.clone(), limit), limit);
+        }
+    }
 
-        let mut new_possibilites = possibilities.clone();
-        new_possibilites.update_found(x, y, n);
+    #[test]
+    fn count_with_zero_limit_is_zero() {
+        assert_eq!(count_solutions(puzzle(UNIQUE), 0), 0);
+    }
 
-        recursively_attempt(new_possibilites, solution, to_revert_stack);
+    #[test]
+    fn count_of_invalid_grid_is_zero() {
+        let mut invalid = puzzle(UNIQUE);
+        invalid.set(0, 0, 5);
+        assert!(!invalid.is_valid());
+        assert_eq!(count_solutions(invalid, usize::MAX), 0);
+    }
 
-        if solution.solved() {
-            return;
+    #[test]
+    fn counts_every_solution() {
+        assert_eq!(count_solutions(puzzle(SOLVED), usize::MAX), 1);
+        assert_eq!(count_solutions(without_rows(2), usize::MAX), 4);
+        assert_eq!(count_solutions(without_rows(3), usize::MAX), 180);
+        assert_eq!(count_solutions(without_rows(3), 10), 10);
+    }
+
+    #[test]
+    fn uniqueness() {
+        assert!(has_unique_solution(puzzle(UNIQUE)));
+        assert_eq!(solve_backtracking(puzzle(UNIQUE)), Some(puzzle(SOLVED)));
+        assert!(!has_unique_solution(without_rows(2)));
+    }
+
+    #[test]
+    fn solve_reports_clashing_givens() {
+        let clashing = puzzle("852976243679143285.3125876931452789676839145.9256..371543862917197435.28286719534");
+        assert_eq!(
+            solve(clashing),
+            SolveOutcome::InvalidGivens { conflicts: vec![(2, 0), (6, 0), (6, 1)] }
+        );
+    }
+
+    #[test]
+    fn solve_reports_contradiction() {
+        let stuck = puzzle("37458.6298257964.1691234875142.53798786942153953817246469175382517328.642386.9517");
+        assert_eq!(solve(stuck), SolveOutcome::Contradiction);
+    }
+
+    #[test]
+    fn solve_reports_unsolvable() {
+        let unsolvable = puzzle("1..7......32.........6......8...2.7.5.7..1........361.7.....2.9....5....3....4..5");
+        assert_eq!(solve(unsolvable), SolveOutcome::Unsolvable);
+    }
+
+    #[test]
+    fn solve_reports_multiple_solutions() {
+        let one_clue = Solution::load_string(include_str!("../data/sudoku2.txt")).unwrap();
+        match solve(one_clue) {
+            SolveOutcome::MultipleSolutions(first, second) => {
+                assert_ne!(first, second);
+                assert!(first.solved() && first.is_valid());
+                assert!(second.solved() && second.is_valid());
+            }
+            outcome => panic!("unexpected outcome {outcome:?}"),
         }
     }
 
-    solution.undo(to_revert_stack, prev_to_revert_stack);
+    #[test]
+    fn solve_reports_solution() {
+        assert_eq!(solve(puzzle(UNIQUE)), SolveOutcome::Solved(puzzle(SOLVED)));
+    }
 }
@@ -0,0 +1,389 @@
+use crate::board::Possibilities;
+use crate::solution::Solution;
+use crate::techniques::{candidates, Candidate, Step, Technique};
+use crate::validation::Unit;
+use itertools::Itertools;
+
+/// How the base lines of a [`Fish`] fit into its cover lines
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FishKind {
+    /// Every base candidate lies in the cover
+    Basic,
+    /// Some base candidates (the fins) lie outside the cover, all in one box
+    Finned,
+    /// Finned, and some base line has a single candidate left in the cover
+    Sashimi,
+}
+
+/// A single-digit fish: `size` base lines whose candidates for `digit` all
+/// lie in `size` cover lines, removing the digit from the rest of the cover.
+///
+/// Finned and sashimi fish only remove the digit from cover cells in the box
+/// holding the fins, as one of those must be true otherwise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fish {
+    pub digit: u8,
+    pub size: usize,
+    pub kind: FishKind,
+    pub base: Vec<Unit>,
+    pub cover: Vec<Unit>,
+    /// `(x, y)` positions of the digit's candidates in the base lines, other
+    /// than the fins
+    pub cells: Vec<(usize, usize)>,
+    /// `(x, y)` positions of base candidates outside the cover
+    pub fins: Vec<(usize, usize)>,
+    pub eliminations: Vec<Candidate>,
+}
+
+impl Fish {
+    pub fn technique(&self) -> Technique {
+        match (self.kind, self.size) {
+            (FishKind::Basic, 2) => Technique::XWing,
+            (FishKind::Basic, 3) => Technique::Swordfish,
+            (FishKind::Basic, 4) => Technique::Jellyfish,
+            (FishKind::Finned, 2) => Technique::FinnedXWing,
+            (FishKind::Finned, 3) => Technique::FinnedSwordfish,
+            (FishKind::Finned, 4) => Technique::FinnedJellyfish,
+            (FishKind::Sashimi, 2) => Technique::SashimiXWing,
+            (FishKind::Sashimi, 3) => Technique::SashimiSwordfish,
+            (FishKind::Sashimi, 4) => Technique::SashimiJellyfish,
+            _ => unreachable!("Fish are only searched for in sizes 2 to 4"),
+        }
+    }
+
+    pub fn to_step(&self) -> Step {
+        Step {
+            technique: self.technique(),
+            digits: vec![self.digit],
+            units: self.base.iter().chain(&self.cover).copied().collect(),
+            cells: self.cells.clone(),
+            fins: self.fins.clone(),
+            placements: vec![],
+            eliminations: self.eliminations.clone(),
+        }
+    }
+}
+
+/// For each row, a bitmask of the columns that can hold `digit`
+pub(crate) fn digit_bitboard(solution: &Solution, possibilities: &Possibilities, digit: u8) -> [u16; 9] {
+    let mut board = [0; 9];
+    for (y, row) in board.iter_mut().enumerate() {
+        for x in 0..9 {
+            if candidates(solution, possibilities, x, y).has(digit) {
+                *row |= 1 << x;
+            }
+        }
+    }
+    board
+}
+
+pub(crate) fn transpose_bitboard(board: &[u16; 9]) -> [u16; 9] {
+    let mut transposed = [0; 9];
+    for (y, row) in board.iter().enumerate() {
+        for (x, col) in transposed.iter_mut().enumerate() {
+            if row & (1 << x) != 0 {
+                *col |= 1 << y;
+            }
+        }
+    }
+    transposed
+}
+
+/// Cell shared by a row and a column
+fn intersection(a: Unit, b: Unit) -> (usize, usize) {
+    match (a, b) {
+        (Unit::Row(y), Unit::Column(x)) | (Unit::Column(x), Unit::Row(y)) => (x, y),
+        _ => unreachable!("Fish lines are a row and a column"),
+    }
+}
+
+/// Finds a basic fish of `size` with rows or columns as its base.
+///
+/// Only sizes 2 to 4 (X-Wing to Jellyfish) are searched for, any other size
+/// finds nothing.
+pub fn find_fish(solution: &Solution, possibilities: &Possibilities, size: usize) -> Option<Fish> {
+    if !(2..=4).contains(&size) {
+        return None;
+    }
+
+    for digit in 0..9 {
+        let rows = digit_bitboard(solution, possibilities, digit);
+        let cols = transpose_bitboard(&rows);
+
+        for (board, base_unit, cover_unit) in [
+            (&rows, Unit::Row as fn(usize) -> Unit, Unit::Column as fn(usize) -> Unit),
+            (&cols, Unit::Column, Unit::Row),
+        ] {
+            let open = (0..9)
+                .filter(|&i| (2..=size as u32).contains(&board[i].count_ones()))
+                .collect_vec();
+
+            for base in open.into_iter().combinations(size) {
+                let cover = base.iter().fold(0, |c, &i| c | board[i]);
+                if cover.count_ones() as usize != size {
+                    continue;
+                }
+
+                let mut eliminations = Vec::new();
+                for line in (0..9).filter(|l| !base.contains(l)) {
+                    for other in (0..9).filter(|o| board[line] & cover & (1 << o) != 0) {
+                        let (x, y) = intersection(base_unit(line), cover_unit(other));
+                        eliminations.push(Candidate::new(x, y, digit));
+                    }
+                }
+
+                if !eliminations.is_empty() {
+                    let mut cells = Vec::new();
+                    for &line in &base {
+                        for other in (0..9).filter(|o| board[line] & (1 << o) != 0) {
+                            cells.push(intersection(base_unit(line), cover_unit(other)));
+                        }
+                    }
+
+                    return Some(Fish {
+                        digit,
+                        size,
+                        kind: FishKind::Basic,
+                        base: base.into_iter().map(base_unit).collect(),
+                        cover: (0..9).filter(|o| cover & (1 << o) != 0).map(cover_unit).collect(),
+                        cells,
+                        fins: vec![],
+                        eliminations,
+                    });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds a fish of `size` and `kind` with rows or columns as its base.
+///
+/// [`FishKind::Basic`] is the same as [`find_fish`], and as there, sizes
+/// outside 2 to 4 find nothing.
+pub fn find_finned_fish(solution: &Solution, possibilities: &Possibilities, size: usize, kind: FishKind) -> Option<Fish> {
+    if kind == FishKind::Basic {
+        return find_fish(solution, possibilities, size);
+    }
+    if !(2..=4).contains(&size) {
+        return None;
+    }
+
+    for digit in 0..9 {
+        let rows = digit_bitboard(solution, possibilities, digit);
+        let cols = transpose_bitboard(&rows);
+
+        for (board, base_unit, cover_unit) in [
+            (&rows, Unit::Row as fn(usize) -> Unit, Unit::Column as fn(usize) -> Unit),
+            (&cols, Unit::Column, Unit::Row),
+        ] {
+            // Fins share a box, so a base line has at most 3 of them
+            let open = (0..9)
+                .filter(|&i| (1..=size as u32 + 3).contains(&board[i].count_ones()))
+                .collect_vec();
+
+            for base in open.into_iter().combinations(size) {
+                let union = base.iter().fold(0u16, |c, &i| c | board[i]);
+                if union.count_ones() as usize <= size {
+                    continue;
+                }
+
+                for cover in (0..9).filter(|o| union & (1 << o) != 0).combinations(size) {
+                    let cover = cover.iter().fold(0u16, |c, &o| c | (1 << o));
+                    if base.iter().any(|&line| board[line] & cover == 0) {
+                        continue;
+                    }
+
+                    let sashimi = base.iter().any(|&line| (board[line] & cover).count_ones() == 1);
+                    if sashimi != (kind == FishKind::Sashimi) {
+                        continue;
+                    }
+
+                    let mut fins = Vec::new();
+                    for &line in &base {
+                        for other in (0..9).filter(|o| board[line] & !cover & (1 << o) != 0) {
+                            fins.push(intersection(base_unit(line), cover_unit(other)));
+                        }
+                    }
+                    let fin_box = box_of(fins[0]);
+                    if fins.iter().any(|&fin| box_of(fin) != fin_box) {
+                        continue;
+                    }
+
+                    let mut eliminations = Vec::new();
+                    for line in (0..9).filter(|l| !base.contains(l)) {
+                        for other in (0..9).filter(|o| board[line] & cover & (1 << o) != 0) {
+                            let (x, y) = intersection(base_unit(line), cover_unit(other));
+                            if box_of((x, y)) == fin_box {
+                                eliminations.push(Candidate::new(x, y, digit));
+                            }
+                        }
+                    }
+
+                    if !eliminations.is_empty() {
+                        let mut cells = Vec::new();
+                        for &line in &base {
+                            for other in (0..9).filter(|o| board[line] & cover & (1 << o) != 0) {
+                                cells.push(intersection(base_unit(line), cover_unit(other)));
+                            }
+                        }
+
+                        return Some(Fish {
+                            digit,
+                            size,
+                            kind,
+                            base: base.into_iter().map(base_unit).collect(),
+                            cover: (0..9).filter(|o| cover & (1 << o) != 0).map(cover_unit).collect(),
+                            cells,
+                            fins,
+                            eliminations,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn box_of((x, y): (usize, usize)) -> usize {
+    y / 3 * 3 + x / 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::iproduct;
+
+    /// Full candidates, except `digit` which is only kept in `lines` at the
+    /// given positions across them
+    fn fish_grid(digit: u8, lines: &[(usize, &[usize])], rows: bool) -> Possibilities {
+        let mut possibilities = Possibilities::new_full();
+        for &(line, keep) in lines {
+            for other in (0..9).filter(|o| !keep.contains(o)) {
+                let (x, y) = if rows { (other, line) } else { (line, other) };
+                possibilities.remove(x, y, digit);
+            }
+        }
+        possibilities
+    }
+
+    #[test]
+    fn x_wing() {
+        let possibilities = fish_grid(0, &[(1, &[2, 6]), (4, &[2, 6])], true);
+
+        let fish = find_fish(&Solution::new_empty(), &possibilities, 2).unwrap();
+        assert_eq!(fish.technique(), Technique::XWing);
+        assert_eq!(fish.base, [Unit::Row(1), Unit::Row(4)]);
+        assert_eq!(fish.cover, [Unit::Column(2), Unit::Column(6)]);
+        assert_eq!(fish.cells, [(2, 1), (6, 1), (2, 4), (6, 4)]);
+        let expected = iproduct!([0, 2, 3, 5, 6, 7, 8], [2, 6])
+            .map(|(y, x)| Candidate::new(x, y, 0))
+            .collect_vec();
+        assert_eq!(fish.eliminations, expected);
+    }
+
+    #[test]
+    fn swordfish() {
+        let possibilities = fish_grid(4, &[(0, &[1, 4]), (3, &[4, 8]), (7, &[1, 8])], true);
+
+        let solution = Solution::new_empty();
+        assert_eq!(find_fish(&solution, &possibilities, 2), None);
+
+        let fish = find_fish(&solution, &possibilities, 3).unwrap();
+        assert_eq!(fish.technique(), Technique::Swordfish);
+        assert_eq!(fish.cover, [Unit::Column(1), Unit::Column(4), Unit::Column(8)]);
+        let expected = iproduct!([1, 2, 4, 5, 6, 8], [1, 4, 8])
+            .map(|(y, x)| Candidate::new(x, y, 4))
+            .collect_vec();
+        assert_eq!(fish.eliminations, expected);
+    }
+
+    #[test]
+    fn jellyfish_on_columns() {
+        let possibilities = fish_grid(8, &[(0, &[1, 3]), (2, &[3, 5]), (5, &[5, 8]), (7, &[1, 8])], false);
+
+        let fish = find_fish(&Solution::new_empty(), &possibilities, 4).unwrap();
+        assert_eq!(fish.technique(), Technique::Jellyfish);
+        assert_eq!(fish.base, [0, 2, 5, 7].map(Unit::Column));
+        assert_eq!(fish.cover, [1, 3, 5, 8].map(Unit::Row));
+        let expected = iproduct!([1, 3, 4, 6, 8], [1, 3, 5, 8])
+            .map(|(x, y)| Candidate::new(x, y, 8))
+            .collect_vec();
+        assert_eq!(fish.eliminations, expected);
+    }
+
+    #[test]
+    fn finned_x_wing() {
+        let possibilities = fish_grid(0, &[(1, &[2, 6]), (4, &[2, 6, 7])], true);
+
+        let solution = Solution::new_empty();
+        assert_eq!(find_fish(&solution, &possibilities, 2), None);
+        assert_eq!(find_finned_fish(&solution, &possibilities, 2, FishKind::Sashimi), None);
+
+        let fish = find_finned_fish(&solution, &possibilities, 2, FishKind::Finned).unwrap();
+        assert_eq!(fish.technique(), Technique::FinnedXWing);
+        assert_eq!(fish.cover, [Unit::Column(2), Unit::Column(6)]);
+        assert_eq!(fish.cells, [(2, 1), (6, 1), (2, 4), (6, 4)]);
+        assert_eq!(fish.fins, [(7, 4)]);
+        assert_eq!(fish.eliminations, [Candidate::new(6, 3, 0), Candidate::new(6, 5, 0)]);
+    }
+
+    #[test]
+    fn sashimi_x_wing() {
+        let possibilities = fish_grid(0, &[(1, &[2, 6]), (4, &[6, 7])], true);
+
+        let solution = Solution::new_empty();
+        assert_eq!(find_finned_fish(&solution, &possibilities, 2, FishKind::Finned), None);
+
+        let fish = find_finned_fish(&solution, &possibilities, 2, FishKind::Sashimi).unwrap();
+        assert_eq!(fish.technique(), Technique::SashimiXWing);
+        assert_eq!(fish.cover, [Unit::Column(2), Unit::Column(6)]);
+        assert_eq!(fish.cells, [(2, 1), (6, 1), (6, 4)]);
+        assert_eq!(fish.fins, [(7, 4)]);
+        assert_eq!(fish.eliminations, [Candidate::new(6, 3, 0), Candidate::new(6, 5, 0)]);
+    }
+
+    #[test]
+    fn finned_swordfish() {
+        let possibilities = fish_grid(3, &[(0, &[1, 5]), (4, &[5, 8]), (7, &[1, 2, 8])], true);
+
+        let fish = find_finned_fish(&Solution::new_empty(), &possibilities, 3, FishKind::Finned).unwrap();
+        assert_eq!(fish.technique(), Technique::FinnedSwordfish);
+        assert_eq!(fish.cover, [Unit::Column(1), Unit::Column(5), Unit::Column(8)]);
+        assert_eq!(fish.fins, [(2, 7)]);
+        assert_eq!(fish.eliminations, [Candidate::new(1, 6, 3), Candidate::new(1, 8, 3)]);
+    }
+
+    #[test]
+    fn fins_in_different_boxes_eliminate_nothing() {
+        let possibilities = fish_grid(0, &[(1, &[2, 6, 0]), (4, &[2, 6, 7])], true);
+
+        let solution = Solution::new_empty();
+        assert_eq!(find_finned_fish(&solution, &possibilities, 2, FishKind::Finned), None);
+        assert_eq!(find_finned_fish(&solution, &possibilities, 2, FishKind::Sashimi), None);
+    }
+
+    #[test]
+    fn basic_kind_finds_basic_fish() {
+        let possibilities = fish_grid(0, &[(1, &[2, 6]), (4, &[2, 6])], true);
+
+        let solution = Solution::new_empty();
+        assert_eq!(
+            find_finned_fish(&solution, &possibilities, 2, FishKind::Basic),
+            find_fish(&solution, &possibilities, 2)
+        );
+    }
+
+    #[test]
+    fn unsupported_sizes_find_nothing() {
+        let possibilities = Possibilities::new_full();
+        let solution = Solution::new_empty();
+        for size in [0, 1, 5, 9] {
+            assert_eq!(find_fish(&solution, &possibilities, size), None);
+            assert_eq!(find_finned_fish(&solution, &possibilities, size, FishKind::Finned), None);
+        }
+    }
+}
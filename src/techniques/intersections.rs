@@ -0,0 +1,186 @@
+use crate::board::Possibilities;
+use crate::solution::Solution;
+use crate::techniques::{candidates, Candidate, Step, Technique};
+use crate::validation::Unit;
+
+/// Bitmask of the cells of `unit` that can hold `digit`, in reading order
+fn positions(solution: &Solution, possibilities: &Possibilities, unit: Unit, digit: u8) -> u16 {
+    let mut mask = 0;
+    for (i, (x, y)) in unit.cells().into_iter().enumerate() {
+        if candidates(solution, possibilities, x, y).has(digit) {
+            mask |= 1 << i;
+        }
+    }
+    mask
+}
+
+/// Line through every set position of a box mask, if there is one
+fn box_line(b: usize, mask: u16) -> Option<Unit> {
+    const ROW: u16 = 0b000_000_111;
+    const COL: u16 = 0b001_001_001;
+
+    (0..3)
+        .find(|r| mask & !(ROW << (r * 3)) == 0)
+        .map(|r| Unit::Row((b / 3) * 3 + r))
+        .or_else(|| {
+            (0..3)
+                .find(|c| mask & !(COL << c) == 0)
+                .map(|c| Unit::Column((b % 3) * 3 + c))
+        })
+}
+
+/// Removes `digit` from every cell of `target` outside `source`
+fn eliminations_outside(
+    solution: &Solution,
+    possibilities: &Possibilities,
+    target: Unit,
+    source: Unit,
+    digit: u8,
+) -> Vec<Candidate> {
+    let source_cells = source.cells();
+    target
+        .cells()
+        .into_iter()
+        .filter(|c| !source_cells.contains(c))
+        .filter(|&(x, y)| candidates(solution, possibilities, x, y).has(digit))
+        .map(|(x, y)| Candidate::new(x, y, digit))
+        .collect()
+}
+
+fn cells_of(unit: Unit, mask: u16) -> Vec<(usize, usize)> {
+    let cells = unit.cells();
+    (0..9).filter(|i| mask & (1 << i) != 0).map(|i| cells[i]).collect()
+}
+
+/// Pointing: a digit confined to one row or column within a box can't
+/// appear elsewhere in that row or column
+pub fn find_pointing(solution: &Solution, possibilities: &Possibilities) -> Option<Step> {
+    for b in 0..9 {
+        let unit = Unit::Box(b);
+        for digit in 0..9 {
+            let mask = positions(solution, possibilities, unit, digit);
+            if mask.count_ones() < 2 {
+                continue;
+            }
+            let Some(line) = box_line(b, mask) else { continue; };
+
+            let eliminations = eliminations_outside(solution, possibilities, line, unit, digit);
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique: Technique::LockedPointing,
+                    digits: vec![digit],
+                    units: vec![unit, line],
+                    cells: cells_of(unit, mask),
+                    fins: vec![],
+                    placements: vec![],
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Claiming: a digit confined to one box within a row or column can't
+/// appear elsewhere in that box
+pub fn find_claiming(solution: &Solution, possibilities: &Possibilities) -> Option<Step> {
+    const SEGMENT: u16 = 0b111;
+
+    for line in (0..9).map(Unit::Row).chain((0..9).map(Unit::Column)) {
+        for digit in 0..9 {
+            let mask = positions(solution, possibilities, line, digit);
+            if mask.count_ones() < 2 {
+                continue;
+            }
+            let Some(segment) = (0..3).find(|s| mask & !(SEGMENT << (s * 3)) == 0) else {
+                continue;
+            };
+
+            let b = match line {
+                Unit::Row(y) => (y / 3) * 3 + segment,
+                Unit::Column(x) => segment * 3 + x / 3,
+                Unit::Box(_) => unreachable!(),
+            };
+            let unit = Unit::Box(b);
+
+            let eliminations = eliminations_outside(solution, possibilities, unit, line, digit);
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique: Technique::LockedClaiming,
+                    digits: vec![digit],
+                    units: vec![line, unit],
+                    cells: cells_of(line, mask),
+                    fins: vec![],
+                    placements: vec![],
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::iproduct;
+
+    #[test]
+    fn pointing() {
+        let mut possibilities = Possibilities::new_full();
+        for (x, y) in [(0, 1), (1, 1), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            possibilities.remove(x, y, 0);
+        }
+
+        let step = find_pointing(&Solution::new_empty(), &possibilities).unwrap();
+        assert_eq!(step.units, [Unit::Box(0), Unit::Row(0)]);
+        assert_eq!(step.cells, [(0, 0), (1, 0), (2, 0)]);
+        let expected: Vec<_> = (3..9).map(|x| Candidate::new(x, 0, 0)).collect();
+        assert_eq!(step.eliminations, expected);
+    }
+
+    #[test]
+    fn pointing_along_a_column() {
+        let mut possibilities = Possibilities::new_full();
+        for (x, y) in Unit::Box(4).cells().into_iter().filter(|&(x, _)| x != 5) {
+            possibilities.remove(x, y, 7);
+        }
+
+        let step = find_pointing(&Solution::new_empty(), &possibilities).unwrap();
+        assert_eq!(step.units, [Unit::Box(4), Unit::Column(5)]);
+        let expected: Vec<_> = [0, 1, 2, 6, 7, 8].into_iter().map(|y| Candidate::new(5, y, 7)).collect();
+        assert_eq!(step.eliminations, expected);
+    }
+
+    #[test]
+    fn claiming() {
+        let mut possibilities = Possibilities::new_full();
+        for x in 3..9 {
+            possibilities.remove(x, 0, 0);
+        }
+
+        let step = find_claiming(&Solution::new_empty(), &possibilities).unwrap();
+        assert_eq!(step.units, [Unit::Row(0), Unit::Box(0)]);
+        assert_eq!(step.cells, [(0, 0), (1, 0), (2, 0)]);
+        let expected: Vec<_> = [(0, 1), (1, 1), (2, 1), (0, 2), (1, 2), (2, 2)]
+            .into_iter()
+            .map(|(x, y)| Candidate::new(x, y, 0))
+            .collect();
+        assert_eq!(step.eliminations, expected);
+    }
+
+    #[test]
+    fn claiming_from_a_column() {
+        let mut possibilities = Possibilities::new_full();
+        for y in (0..9).filter(|y| !(6..9).contains(y)) {
+            possibilities.remove(4, y, 3);
+        }
+
+        let step = find_claiming(&Solution::new_empty(), &possibilities).unwrap();
+        assert_eq!(step.units, [Unit::Column(4), Unit::Box(7)]);
+        let expected: Vec<_> = iproduct!(6..9, [3, 5]).map(|(y, x)| Candidate::new(x, y, 3)).collect();
+        assert_eq!(step.eliminations, expected);
+    }
+}
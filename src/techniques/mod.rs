@@ -0,0 +1,284 @@
+use crate::board::{Possibilities, SudokuPossibility};
+use crate::solution::Solution;
+use crate::validation::Unit;
+use std::fmt::{Display, Formatter};
+
+pub use fish::{find_finned_fish, find_fish, Fish, FishKind};
+
+mod fish;
+mod intersections;
+mod singles;
+mod subsets;
+mod wings;
+
+/// A logical deduction technique, in increasing order of difficulty
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Technique {
+    NakedSingle,
+    HiddenSingle,
+    LockedPointing,
+    LockedClaiming,
+    NakedPair,
+    XWing,
+    HiddenPair,
+    FinnedXWing,
+    SashimiXWing,
+    NakedTriple,
+    Swordfish,
+    HiddenTriple,
+    FinnedSwordfish,
+    SashimiSwordfish,
+    XYWing,
+    XYZWing,
+    WWing,
+    NakedQuad,
+    Jellyfish,
+    HiddenQuad,
+    FinnedJellyfish,
+    SashimiJellyfish,
+}
+
+impl Technique {
+    pub const ALL: &'static [Technique] = &[
+        Technique::NakedSingle,
+        Technique::HiddenSingle,
+        Technique::LockedPointing,
+        Technique::LockedClaiming,
+        Technique::NakedPair,
+        Technique::XWing,
+        Technique::HiddenPair,
+        Technique::FinnedXWing,
+        Technique::SashimiXWing,
+        Technique::NakedTriple,
+        Technique::Swordfish,
+        Technique::HiddenTriple,
+        Technique::FinnedSwordfish,
+        Technique::SashimiSwordfish,
+        Technique::XYWing,
+        Technique::XYZWing,
+        Technique::WWing,
+        Technique::NakedQuad,
+        Technique::Jellyfish,
+        Technique::HiddenQuad,
+        Technique::FinnedJellyfish,
+        Technique::SashimiJellyfish,
+    ];
+
+    /// Pointing and claiming
+    pub const LOCKED_CANDIDATES: &'static [Technique] = &[Technique::LockedPointing, Technique::LockedClaiming];
+
+    /// Naked and hidden pairs, triples and quads
+    pub const SUBSETS: &'static [Technique] = &[
+        Technique::NakedPair,
+        Technique::HiddenPair,
+        Technique::NakedTriple,
+        Technique::HiddenTriple,
+        Technique::NakedQuad,
+        Technique::HiddenQuad,
+    ];
+
+    /// X-Wing, Swordfish and Jellyfish
+    pub const FISH: &'static [Technique] = &[Technique::XWing, Technique::Swordfish, Technique::Jellyfish];
+
+    /// Finned and sashimi X-Wing, Swordfish and Jellyfish
+    pub const FINNED_FISH: &'static [Technique] = &[
+        Technique::FinnedXWing,
+        Technique::SashimiXWing,
+        Technique::FinnedSwordfish,
+        Technique::SashimiSwordfish,
+        Technique::FinnedJellyfish,
+        Technique::SashimiJellyfish,
+    ];
+
+    /// XY-Wing, XYZ-Wing and W-Wing
+    pub const WINGS: &'static [Technique] = &[Technique::XYWing, Technique::XYZWing, Technique::WWing];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Technique::NakedSingle => "Naked Single",
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::LockedPointing => "Locked Candidates (Pointing)",
+            Technique::LockedClaiming => "Locked Candidates (Claiming)",
+            Technique::NakedPair => "Naked Pair",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::NakedQuad => "Naked Quad",
+            Technique::HiddenQuad => "Hidden Quad",
+            Technique::XWing => "X-Wing",
+            Technique::Swordfish => "Swordfish",
+            Technique::Jellyfish => "Jellyfish",
+            Technique::FinnedXWing => "Finned X-Wing",
+            Technique::SashimiXWing => "Sashimi X-Wing",
+            Technique::FinnedSwordfish => "Finned Swordfish",
+            Technique::SashimiSwordfish => "Sashimi Swordfish",
+            Technique::FinnedJellyfish => "Finned Jellyfish",
+            Technique::SashimiJellyfish => "Sashimi Jellyfish",
+            Technique::XYWing => "XY-Wing",
+            Technique::XYZWing => "XYZ-Wing",
+            Technique::WWing => "W-Wing",
+        }
+    }
+
+    /// Difficulty on a scale loosely following Sudoku Explainer's ratings
+    pub fn difficulty(self) -> f32 {
+        match self {
+            Technique::NakedSingle => 1.0,
+            Technique::HiddenSingle => 1.5,
+            Technique::LockedPointing => 2.6,
+            Technique::LockedClaiming => 2.8,
+            Technique::NakedPair => 3.0,
+            Technique::HiddenPair => 3.4,
+            Technique::NakedTriple => 3.6,
+            Technique::HiddenTriple => 4.0,
+            Technique::NakedQuad => 5.0,
+            Technique::HiddenQuad => 5.4,
+            Technique::XWing => 3.2,
+            Technique::Swordfish => 3.8,
+            Technique::Jellyfish => 5.2,
+            Technique::FinnedXWing => 3.4,
+            Technique::SashimiXWing => 3.5,
+            Technique::FinnedSwordfish => 4.0,
+            Technique::SashimiSwordfish => 4.1,
+            Technique::FinnedJellyfish => 5.4,
+            Technique::SashimiJellyfish => 5.5,
+            Technique::XYWing => 4.2,
+            Technique::XYZWing => 4.4,
+            Technique::WWing => 4.4,
+        }
+    }
+
+    /// Score added per use, loosely following HoDoKu's step scores
+    pub fn score(self) -> u32 {
+        match self {
+            Technique::NakedSingle => 4,
+            Technique::HiddenSingle => 14,
+            Technique::LockedPointing => 50,
+            Technique::LockedClaiming => 50,
+            Technique::NakedPair => 60,
+            Technique::HiddenPair => 70,
+            Technique::NakedTriple => 80,
+            Technique::HiddenTriple => 100,
+            Technique::NakedQuad => 120,
+            Technique::HiddenQuad => 150,
+            Technique::XWing => 140,
+            Technique::Swordfish => 150,
+            Technique::Jellyfish => 160,
+            Technique::FinnedXWing => 130,
+            Technique::SashimiXWing => 150,
+            Technique::FinnedSwordfish => 200,
+            Technique::SashimiSwordfish => 240,
+            Technique::FinnedJellyfish => 240,
+            Technique::SashimiJellyfish => 280,
+            Technique::XYWing => 160,
+            Technique::XYZWing => 180,
+            Technique::WWing => 150,
+        }
+    }
+
+    /// Finds the first application of this technique on the grid
+    pub fn find(self, solution: &Solution, possibilities: &Possibilities) -> Option<Step> {
+        match self {
+            Technique::NakedSingle => singles::find_naked_single(solution, possibilities),
+            Technique::HiddenSingle => singles::find_hidden_single(solution, possibilities),
+            Technique::LockedPointing => intersections::find_pointing(solution, possibilities),
+            Technique::LockedClaiming => intersections::find_claiming(solution, possibilities),
+            Technique::NakedPair => subsets::find_naked_subset(solution, possibilities, 2, self),
+            Technique::HiddenPair => subsets::find_hidden_subset(solution, possibilities, 2, self),
+            Technique::NakedTriple => subsets::find_naked_subset(solution, possibilities, 3, self),
+            Technique::HiddenTriple => subsets::find_hidden_subset(solution, possibilities, 3, self),
+            Technique::NakedQuad => subsets::find_naked_subset(solution, possibilities, 4, self),
+            Technique::HiddenQuad => subsets::find_hidden_subset(solution, possibilities, 4, self),
+            Technique::XWing => find_fish(solution, possibilities, 2).map(|f| f.to_step()),
+            Technique::Swordfish => find_fish(solution, possibilities, 3).map(|f| f.to_step()),
+            Technique::Jellyfish => find_fish(solution, possibilities, 4).map(|f| f.to_step()),
+            Technique::FinnedXWing => find_finned_fish(solution, possibilities, 2, FishKind::Finned).map(|f| f.to_step()),
+            Technique::SashimiXWing => find_finned_fish(solution, possibilities, 2, FishKind::Sashimi).map(|f| f.to_step()),
+            Technique::FinnedSwordfish => find_finned_fish(solution, possibilities, 3, FishKind::Finned).map(|f| f.to_step()),
+            Technique::SashimiSwordfish => find_finned_fish(solution, possibilities, 3, FishKind::Sashimi).map(|f| f.to_step()),
+            Technique::FinnedJellyfish => find_finned_fish(solution, possibilities, 4, FishKind::Finned).map(|f| f.to_step()),
+            Technique::SashimiJellyfish => find_finned_fish(solution, possibilities, 4, FishKind::Sashimi).map(|f| f.to_step()),
+            Technique::XYWing => wings::find_xy_wing(solution, possibilities),
+            Technique::XYZWing => wings::find_xyz_wing(solution, possibilities),
+            Technique::WWing => wings::find_w_wing(solution, possibilities),
+        }
+    }
+}
+
+impl Display for Technique {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A digit at a cell, either placed or eliminated by a [`Step`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Candidate {
+    pub x: usize,
+    pub y: usize,
+    pub digit: u8,
+}
+
+impl Candidate {
+    #[inline]
+    pub const fn new(x: usize, y: usize, digit: u8) -> Self {
+        Candidate { x, y, digit }
+    }
+}
+
+/// A single deduction, with the pattern that justifies it and its effects
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub technique: Technique,
+    /// Digits forming the pattern
+    pub digits: Vec<u8>,
+    /// Units the pattern lies in
+    pub units: Vec<Unit>,
+    /// `(x, y)` positions of the cells forming the pattern
+    pub cells: Vec<(usize, usize)>,
+    /// `(x, y)` positions of fins, candidates outside the pattern that limit
+    /// its eliminations to the cells seeing them
+    pub fins: Vec<(usize, usize)>,
+    pub placements: Vec<Candidate>,
+    pub eliminations: Vec<Candidate>,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:", self.technique)?;
+
+        for (i, digit) in self.digits.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { " " } else { "/" }, digit + 1)?;
+        }
+        for (i, unit) in self.units.iter().enumerate() {
+            write!(f, "{}{unit}", if i == 0 { " in " } else { ", " })?;
+        }
+        for (i, (x, y)) in self.cells.iter().enumerate() {
+            write!(f, "{}r{}c{}", if i == 0 { " at " } else { ", " }, y + 1, x + 1)?;
+        }
+        for (i, (x, y)) in self.fins.iter().enumerate() {
+            write!(f, "{}r{}c{}", if i == 0 { " with fins " } else { ", " }, y + 1, x + 1)?;
+        }
+
+        write!(f, " =>")?;
+        for (i, c) in self.placements.iter().enumerate() {
+            write!(f, "{} r{}c{}={}", if i == 0 { "" } else { "," }, c.y + 1, c.x + 1, c.digit + 1)?;
+        }
+        for (i, c) in self.eliminations.iter().enumerate() {
+            let sep = if i == 0 && self.placements.is_empty() { "" } else { "," };
+            write!(f, "{sep} r{}c{}<>{}", c.y + 1, c.x + 1, c.digit + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Candidates of a cell, empty if the cell is already filled
+#[inline]
+pub(crate) fn candidates(solution: &Solution, possibilities: &Possibilities, x: usize, y: usize) -> SudokuPossibility {
+    if solution.get(x, y) != 9 {
+        SudokuPossibility::new_empty()
+    } else {
+        possibilities.get(x, y)
+    }
+}
@@ -0,0 +1,97 @@
+use crate::board::Possibilities;
+use crate::solution::Solution;
+use crate::techniques::{candidates, Candidate, Step, Technique};
+use crate::validation::Unit;
+use itertools::iproduct;
+
+pub fn find_naked_single(solution: &Solution, possibilities: &Possibilities) -> Option<Step> {
+    for (y, x) in iproduct!(0..9, 0..9) {
+        let cell = candidates(solution, possibilities, x, y);
+        if cell.bits_set() != 1 {
+            continue;
+        }
+
+        let digit = cell.find_single_bit();
+        return Some(Step {
+            technique: Technique::NakedSingle,
+            digits: vec![digit],
+            units: vec![],
+            cells: vec![(x, y)],
+            fins: vec![],
+            placements: vec![Candidate::new(x, y, digit)],
+            eliminations: vec![],
+        });
+    }
+
+    None
+}
+
+pub fn find_hidden_single(solution: &Solution, possibilities: &Possibilities) -> Option<Step> {
+    for unit in Unit::all() {
+        let cells = unit.cells();
+        for digit in 0..9 {
+            let mut found = None;
+            let mut count = 0;
+            for &(x, y) in &cells {
+                if candidates(solution, possibilities, x, y).has(digit) {
+                    found = Some((x, y));
+                    count += 1;
+                }
+            }
+
+            if count != 1 {
+                continue;
+            }
+            let (x, y) = found.unwrap();
+            return Some(Step {
+                technique: Technique::HiddenSingle,
+                digits: vec![digit],
+                units: vec![unit],
+                cells: vec![(x, y)],
+                fins: vec![],
+                placements: vec![Candidate::new(x, y, digit)],
+                eliminations: vec![],
+            });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::SudokuPossibility;
+
+    #[test]
+    fn naked_single() {
+        let mut possibilities = Possibilities::new_full();
+        possibilities.set(3, 4, SudokuPossibility::new_val(6));
+
+        let step = find_naked_single(&Solution::new_empty(), &possibilities).unwrap();
+        assert_eq!(step.placements, [Candidate::new(3, 4, 6)]);
+        assert!(step.eliminations.is_empty());
+    }
+
+    #[test]
+    fn hidden_single() {
+        let mut possibilities = Possibilities::new_full();
+        for x in (0..9).filter(|&x| x != 5) {
+            possibilities.remove(x, 0, 2);
+        }
+
+        let step = find_hidden_single(&Solution::new_empty(), &possibilities).unwrap();
+        assert_eq!(step.units, [Unit::Row(0)]);
+        assert_eq!(step.placements, [Candidate::new(5, 0, 2)]);
+    }
+
+    #[test]
+    fn filled_cells_are_not_singles() {
+        let mut solution = Solution::new_empty();
+        solution.set(3, 4, 6);
+        let mut possibilities = Possibilities::from_solution(&solution);
+        possibilities.set(3, 4, SudokuPossibility::new_val(6));
+
+        assert_eq!(find_naked_single(&solution, &possibilities), None);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::board::{Possibilities, SudokuPossibility};
+use crate::solution::Solution;
+use crate::techniques::{candidates, Candidate, Step, Technique};
+use crate::validation::Unit;
+use itertools::Itertools;
+
+/// Finds `size` cells in a unit whose candidates are limited to `size`
+/// digits, removing those digits from the rest of the unit
+pub fn find_naked_subset(
+    solution: &Solution,
+    possibilities: &Possibilities,
+    size: usize,
+    technique: Technique,
+) -> Option<Step> {
+    for unit in Unit::all() {
+        let cells = unit.cells();
+        let open = cells
+            .iter()
+            .copied()
+            .filter(|&(x, y)| {
+                let count = candidates(solution, possibilities, x, y).bits_set() as usize;
+                count >= 2 && count <= size
+            })
+            .collect_vec();
+
+        for subset in open.iter().copied().combinations(size) {
+            let union = subset.iter().fold(SudokuPossibility::new_empty(), |u, &(x, y)| {
+                u.or(candidates(solution, possibilities, x, y))
+            });
+            if union.bits_set() as usize != size {
+                continue;
+            }
+
+            let digits = (0..9).filter(|d| union.has(*d)).collect_vec();
+            let mut eliminations = Vec::new();
+            for &(x, y) in cells.iter().filter(|c| !subset.contains(c)) {
+                let cell = candidates(solution, possibilities, x, y);
+                for &d in digits.iter().filter(|d| cell.has(**d)) {
+                    eliminations.push(Candidate::new(x, y, d));
+                }
+            }
+
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique,
+                    digits,
+                    units: vec![unit],
+                    cells: subset,
+                    fins: vec![],
+                    placements: vec![],
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds `size` digits confined to `size` cells of a unit, removing every
+/// other digit from those cells
+pub fn find_hidden_subset(
+    solution: &Solution,
+    possibilities: &Possibilities,
+    size: usize,
+    technique: Technique,
+) -> Option<Step> {
+    for unit in Unit::all() {
+        let cells = unit.cells();
+
+        // Bitmask of the unit's cells that can hold each digit
+        let mut positions = [0u16; 9];
+        for (i, &(x, y)) in cells.iter().enumerate() {
+            let cell = candidates(solution, possibilities, x, y);
+            for d in (0..9).filter(|d| cell.has(*d)) {
+                positions[d as usize] |= 1 << i;
+            }
+        }
+
+        let open = (0..9u8)
+            .filter(|&d| {
+                let count = positions[d as usize].count_ones() as usize;
+                count >= 2 && count <= size
+            })
+            .collect_vec();
+
+        for digits in open.iter().copied().combinations(size) {
+            let union = digits.iter().fold(0, |u, &d| u | positions[d as usize]);
+            if union.count_ones() as usize != size {
+                continue;
+            }
+
+            let subset = (0..9)
+                .filter(|i| union & (1 << i) != 0)
+                .map(|i| cells[i])
+                .collect_vec();
+            let mut eliminations = Vec::new();
+            for &(x, y) in &subset {
+                let cell = candidates(solution, possibilities, x, y);
+                for d in (0..9).filter(|d| cell.has(*d) && !digits.contains(d)) {
+                    eliminations.push(Candidate::new(x, y, d));
+                }
+            }
+
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique,
+                    digits,
+                    units: vec![unit],
+                    cells: subset,
+                    fins: vec![],
+                    placements: vec![],
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mask(digits: &[u8]) -> SudokuPossibility {
+        digits.iter().fold(SudokuPossibility::new_empty(), |p, &d| p.or(SudokuPossibility::new_val(d)))
+    }
+
+    #[test]
+    fn naked_pair() {
+        let mut possibilities = Possibilities::new_full();
+        possibilities.set(0, 0, mask(&[1, 2]));
+        possibilities.set(1, 0, mask(&[1, 2]));
+
+        let step = Technique::NakedPair.find(&Solution::new_empty(), &possibilities).unwrap();
+        assert_eq!(step.units, [Unit::Row(0)]);
+        assert_eq!(step.digits, [1, 2]);
+        assert_eq!(step.cells, [(0, 0), (1, 0)]);
+        let expected = (2..9).flat_map(|x| [Candidate::new(x, 0, 1), Candidate::new(x, 0, 2)]).collect_vec();
+        assert_eq!(step.eliminations, expected);
+    }
+
+    #[test]
+    fn naked_triple() {
+        let mut possibilities = Possibilities::new_full();
+        possibilities.set(0, 2, mask(&[3, 5]));
+        possibilities.set(0, 4, mask(&[5, 7]));
+        possibilities.set(0, 7, mask(&[3, 7]));
+
+        let solution = Solution::new_empty();
+        assert_eq!(Technique::NakedPair.find(&solution, &possibilities), None);
+
+        let step = Technique::NakedTriple.find(&solution, &possibilities).unwrap();
+        assert_eq!(step.units, [Unit::Column(0)]);
+        assert_eq!(step.digits, [3, 5, 7]);
+        let expected = [0, 1, 3, 5, 6, 8]
+            .into_iter()
+            .flat_map(|y| [3, 5, 7].map(|d| Candidate::new(0, y, d)))
+            .collect_vec();
+        assert_eq!(step.eliminations, expected);
+    }
+
+    #[test]
+    fn hidden_pair() {
+        let mut possibilities = Possibilities::new_full();
+        for x in [0, 1, 3, 4, 5, 7, 8] {
+            possibilities.remove(x, 0, 3);
+            possibilities.remove(x, 0, 4);
+        }
+
+        let step = Technique::HiddenPair.find(&Solution::new_empty(), &possibilities).unwrap();
+        assert_eq!(step.units, [Unit::Row(0)]);
+        assert_eq!(step.digits, [3, 4]);
+        assert_eq!(step.cells, [(2, 0), (6, 0)]);
+        let expected = [2, 6]
+            .into_iter()
+            .flat_map(|x| [0, 1, 2, 5, 6, 7, 8].map(|d| Candidate::new(x, 0, d)))
+            .collect_vec();
+        assert_eq!(step.eliminations, expected);
+    }
+
+    #[test]
+    fn hidden_quad() {
+        let mut possibilities = Possibilities::new_full();
+        let quad = [(0, 0), (2, 1), (1, 2), (2, 2)];
+        for (x, y) in Unit::Box(0).cells().into_iter().filter(|c| !quad.contains(c)) {
+            for d in [0, 2, 4, 6] {
+                possibilities.remove(x, y, d);
+            }
+        }
+
+        let solution = Solution::new_empty();
+        assert_eq!(Technique::HiddenTriple.find(&solution, &possibilities), None);
+
+        let step = Technique::HiddenQuad.find(&solution, &possibilities).unwrap();
+        assert_eq!(step.units, [Unit::Box(0)]);
+        assert_eq!(step.digits, [0, 2, 4, 6]);
+        assert_eq!(step.cells, quad);
+        let expected = quad
+            .into_iter()
+            .flat_map(|(x, y)| [1, 3, 5, 7, 8].map(|d| Candidate::new(x, y, d)))
+            .collect_vec();
+        assert_eq!(step.eliminations, expected);
+    }
+}
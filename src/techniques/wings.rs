@@ -0,0 +1,228 @@
+use crate::board::{peers, sees, Possibilities};
+use crate::solution::Solution;
+use crate::techniques::{candidates, Candidate, Step, Technique};
+use crate::validation::Unit;
+use itertools::{iproduct, Itertools};
+
+/// Candidates for `digit` in every cell seeing all of `cells`
+fn eliminations_seen_by(solution: &Solution, possibilities: &Possibilities, digit: u8, cells: &[(usize, usize)]) -> Vec<Candidate> {
+    let (x, y) = cells[0];
+    peers(x, y)
+        .filter(|&cell| cells[1..].iter().all(|&other| sees(cell, other)))
+        .filter(|&(x, y)| candidates(solution, possibilities, x, y).has(digit))
+        .map(|(x, y)| Candidate::new(x, y, digit))
+        .collect()
+}
+
+/// Peers of `(x, y)` with exactly two candidates
+fn bivalue_peers(solution: &Solution, possibilities: &Possibilities, x: usize, y: usize) -> Vec<(usize, usize)> {
+    peers(x, y)
+        .filter(|&(x, y)| candidates(solution, possibilities, x, y).bits_set() == 2)
+        .collect()
+}
+
+/// Finds a bivalue pivot `xy` seeing bivalue wings `xz` and `yz`, removing
+/// `z` from every cell seeing both wings
+pub fn find_xy_wing(solution: &Solution, possibilities: &Possibilities) -> Option<Step> {
+    for (y, x) in iproduct!(0..9, 0..9) {
+        let pivot = candidates(solution, possibilities, x, y);
+        if pivot.bits_set() != 2 {
+            continue;
+        }
+
+        for (a, b) in bivalue_peers(solution, possibilities, x, y).into_iter().tuple_combinations() {
+            let wing_a = candidates(solution, possibilities, a.0, a.1).mask();
+            let wing_b = candidates(solution, possibilities, b.0, b.1).mask();
+
+            let z = wing_a & wing_b & !pivot.mask();
+            if z.count_ones() != 1 || (wing_a | wing_b) & pivot.mask() != pivot.mask() {
+                continue;
+            }
+
+            let z = z.trailing_zeros() as u8;
+            let eliminations = eliminations_seen_by(solution, possibilities, z, &[a, b]);
+            if !eliminations.is_empty() {
+                let mut digits = (0..9).filter(|&d| pivot.has(d)).collect_vec();
+                digits.push(z);
+
+                return Some(Step {
+                    technique: Technique::XYWing,
+                    digits,
+                    units: vec![],
+                    cells: vec![(x, y), a, b],
+                    fins: vec![],
+                    placements: vec![],
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds a pivot `xyz` seeing bivalue wings `xz` and `yz`, removing `z` from
+/// every cell seeing the pivot and both wings
+pub fn find_xyz_wing(solution: &Solution, possibilities: &Possibilities) -> Option<Step> {
+    for (y, x) in iproduct!(0..9, 0..9) {
+        let pivot = candidates(solution, possibilities, x, y);
+        if pivot.bits_set() != 3 {
+            continue;
+        }
+
+        let wings = bivalue_peers(solution, possibilities, x, y)
+            .into_iter()
+            .filter(|&(x, y)| candidates(solution, possibilities, x, y).mask() & !pivot.mask() == 0)
+            .collect_vec();
+
+        for (a, b) in wings.into_iter().tuple_combinations() {
+            let wing_a = candidates(solution, possibilities, a.0, a.1).mask();
+            let wing_b = candidates(solution, possibilities, b.0, b.1).mask();
+            if wing_a == wing_b {
+                continue;
+            }
+
+            let z = (wing_a & wing_b).trailing_zeros() as u8;
+            let eliminations = eliminations_seen_by(solution, possibilities, z, &[(x, y), a, b]);
+            if !eliminations.is_empty() {
+                let mut digits = (0..9).filter(|&d| d != z && pivot.has(d)).collect_vec();
+                digits.push(z);
+
+                return Some(Step {
+                    technique: Technique::XYZWing,
+                    digits,
+                    units: vec![],
+                    cells: vec![(x, y), a, b],
+                    fins: vec![],
+                    placements: vec![],
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds two bivalue cells `xy` that don't see each other, joined by a unit
+/// where `x` can only go in a cell seeing one or a cell seeing the other,
+/// removing `y` from every cell seeing both
+pub fn find_w_wing(solution: &Solution, possibilities: &Possibilities) -> Option<Step> {
+    let bivalue = iproduct!(0..9, 0..9)
+        .map(|(y, x)| (x, y))
+        .filter(|&(x, y)| candidates(solution, possibilities, x, y).bits_set() == 2)
+        .collect_vec();
+
+    for (a, b) in bivalue.into_iter().tuple_combinations() {
+        let pair = candidates(solution, possibilities, a.0, a.1);
+        if pair != candidates(solution, possibilities, b.0, b.1) || sees(a, b) {
+            continue;
+        }
+
+        for (link, other) in [(0, 1), (1, 0)] {
+            let digits = (0..9).filter(|&d| pair.has(d)).collect_vec();
+            let (link, other) = (digits[link], digits[other]);
+
+            let eliminations = eliminations_seen_by(solution, possibilities, other, &[a, b]);
+            if eliminations.is_empty() {
+                continue;
+            }
+
+            for unit in Unit::all() {
+                let Some((c, d)) = unit
+                    .cells()
+                    .into_iter()
+                    .filter(|&(x, y)| candidates(solution, possibilities, x, y).has(link))
+                    .collect_tuple()
+                else {
+                    continue;
+                };
+
+                let (c, d) = if sees(c, a) && sees(d, b) {
+                    (c, d)
+                } else if sees(d, a) && sees(c, b) {
+                    (d, c)
+                } else {
+                    continue;
+                };
+
+                return Some(Step {
+                    technique: Technique::WWing,
+                    digits: vec![link, other],
+                    units: vec![unit],
+                    cells: vec![a, c, d, b],
+                    fins: vec![],
+                    placements: vec![],
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::SudokuPossibility;
+
+    fn mask(digits: &[u8]) -> SudokuPossibility {
+        digits.iter().fold(SudokuPossibility::new_empty(), |p, &d| p.or(SudokuPossibility::new_val(d)))
+    }
+
+    #[test]
+    fn xy_wing() {
+        let mut possibilities = Possibilities::new_full();
+        possibilities.set(0, 0, mask(&[0, 1]));
+        possibilities.set(4, 0, mask(&[0, 2]));
+        possibilities.set(1, 1, mask(&[1, 2]));
+
+        let step = find_xy_wing(&Solution::new_empty(), &possibilities).unwrap();
+        assert_eq!(step.digits, [0, 1, 2]);
+        assert_eq!(step.cells, [(0, 0), (4, 0), (1, 1)]);
+        let expected = [(1, 0), (2, 0), (3, 1), (4, 1), (5, 1)].map(|(x, y)| Candidate::new(x, y, 2));
+        assert_eq!(step.eliminations, expected);
+    }
+
+    #[test]
+    fn xyz_wing() {
+        let mut possibilities = Possibilities::new_full();
+        possibilities.set(1, 1, mask(&[0, 1, 2]));
+        possibilities.set(0, 0, mask(&[1, 2]));
+        possibilities.set(5, 1, mask(&[0, 2]));
+
+        let solution = Solution::new_empty();
+        assert_eq!(find_xy_wing(&solution, &possibilities), None);
+
+        let step = find_xyz_wing(&solution, &possibilities).unwrap();
+        assert_eq!(step.digits, [0, 1, 2]);
+        assert_eq!(step.cells, [(1, 1), (0, 0), (5, 1)]);
+        assert_eq!(step.eliminations, [Candidate::new(0, 1, 2), Candidate::new(2, 1, 2)]);
+    }
+
+    #[test]
+    fn w_wing() {
+        let mut possibilities = Possibilities::new_full();
+        possibilities.set(0, 0, mask(&[0, 1]));
+        possibilities.set(4, 4, mask(&[0, 1]));
+        for x in (0..9).filter(|&x| x != 0 && x != 4) {
+            possibilities.remove(x, 8, 0);
+        }
+
+        let step = find_w_wing(&Solution::new_empty(), &possibilities).unwrap();
+        assert_eq!(step.digits, [0, 1]);
+        assert_eq!(step.units, [Unit::Row(8)]);
+        assert_eq!(step.cells, [(0, 0), (0, 8), (4, 8), (4, 4)]);
+        assert_eq!(step.eliminations, [Candidate::new(4, 0, 1), Candidate::new(0, 4, 1)]);
+    }
+
+    #[test]
+    fn w_wing_needs_a_strong_link() {
+        let mut possibilities = Possibilities::new_full();
+        possibilities.set(0, 0, mask(&[0, 1]));
+        possibilities.set(4, 4, mask(&[0, 1]));
+
+        assert_eq!(find_w_wing(&Solution::new_empty(), &possibilities), None);
+    }
+}
@@ -0,0 +1,409 @@
+use crate::board::{Possibilities, SudokuPossibility};
+use crate::solution::Solution;
+use itertools::iproduct;
+
+/// A validity-preserving rearrangement of a grid.
+///
+/// Applied as an optional transpose, then a permutation of rows and of
+/// columns that keeps bands and stacks together, then a relabelling of
+/// digits. Any composition of rotations, mirrors, band, stack, row and column
+/// permutations and relabellings can be represented.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Transform {
+    transpose: bool,
+    /// Source row of each row after transposing
+    rows: [u8; 9],
+    /// Source column of each column after transposing
+    cols: [u8; 9],
+    /// New digit for each digit
+    labels: [u8; 9],
+}
+
+const IDENTITY: [u8; 9] = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+const REVERSED: [u8; 9] = [8, 7, 6, 5, 4, 3, 2, 1, 0];
+
+impl Transform {
+    /// Builds a transform, returning `None` if `rows` or `cols` split up a
+    /// band or stack, or any argument isn't a permutation
+    pub fn new(transpose: bool, rows: [u8; 9], cols: [u8; 9], labels: [u8; 9]) -> Option<Self> {
+        if !is_line_permutation(&rows) || !is_line_permutation(&cols) || !is_permutation(&labels) {
+            return None;
+        }
+        Some(Transform {
+            transpose,
+            rows,
+            cols,
+            labels,
+        })
+    }
+
+    pub const fn identity() -> Self {
+        Transform {
+            transpose: false,
+            rows: IDENTITY,
+            cols: IDENTITY,
+            labels: IDENTITY,
+        }
+    }
+
+    /// Rotates clockwise by 90 degrees
+    pub const fn rotate90() -> Self {
+        Transform {
+            transpose: true,
+            rows: IDENTITY,
+            cols: REVERSED,
+            labels: IDENTITY,
+        }
+    }
+
+    pub const fn rotate180() -> Self {
+        Transform {
+            transpose: false,
+            rows: REVERSED,
+            cols: REVERSED,
+            labels: IDENTITY,
+        }
+    }
+
+    /// Rotates clockwise by 270 degrees
+    pub const fn rotate270() -> Self {
+        Transform {
+            transpose: true,
+            rows: REVERSED,
+            cols: IDENTITY,
+            labels: IDENTITY,
+        }
+    }
+
+    /// Mirrors across the top-left to bottom-right diagonal
+    pub const fn transpose() -> Self {
+        Transform {
+            transpose: true,
+            rows: IDENTITY,
+            cols: IDENTITY,
+            labels: IDENTITY,
+        }
+    }
+
+    /// Mirrors top to bottom
+    pub const fn mirror_horizontal() -> Self {
+        Transform {
+            transpose: false,
+            rows: REVERSED,
+            cols: IDENTITY,
+            labels: IDENTITY,
+        }
+    }
+
+    /// Mirrors left to right
+    pub const fn mirror_vertical() -> Self {
+        Transform {
+            transpose: false,
+            rows: IDENTITY,
+            cols: REVERSED,
+            labels: IDENTITY,
+        }
+    }
+
+    /// Row `i` of `band` becomes the band's row `order[i]`.
+    ///
+    /// # Panics
+    /// If `band` is out of range or `order` isn't a permutation of `0..3`.
+    pub fn permute_rows_in_band(band: usize, order: [usize; 3]) -> Self {
+        Transform {
+            rows: lines_in_band(band, order),
+            ..Self::identity()
+        }
+    }
+
+    /// Band `i` becomes band `order[i]`.
+    ///
+    /// # Panics
+    /// If `order` isn't a permutation of `0..3`.
+    pub fn permute_bands(order: [usize; 3]) -> Self {
+        Transform {
+            rows: bands(order),
+            ..Self::identity()
+        }
+    }
+
+    /// Column `i` of `stack` becomes the stack's column `order[i]`.
+    ///
+    /// # Panics
+    /// If `stack` is out of range or `order` isn't a permutation of `0..3`.
+    pub fn permute_columns_in_stack(stack: usize, order: [usize; 3]) -> Self {
+        Transform {
+            cols: lines_in_band(stack, order),
+            ..Self::identity()
+        }
+    }
+
+    /// Stack `i` becomes stack `order[i]`.
+    ///
+    /// # Panics
+    /// If `order` isn't a permutation of `0..3`.
+    pub fn permute_stacks(order: [usize; 3]) -> Self {
+        Transform {
+            cols: bands(order),
+            ..Self::identity()
+        }
+    }
+
+    /// Digit `d` becomes `labels[d]`.
+    ///
+    /// # Panics
+    /// If `labels` isn't a permutation of `0..9`.
+    pub fn relabel(labels: [u8; 9]) -> Self {
+        assert!(is_permutation(&labels), "Labels must be a permutation of 0..9");
+        Transform {
+            labels,
+            ..Self::identity()
+        }
+    }
+
+    /// Applies `self`, then `next`
+    pub fn then(&self, next: &Transform) -> Transform {
+        let mut rows = [0; 9];
+        let mut cols = [0; 9];
+        for i in 0..9 {
+            if next.transpose {
+                rows[i] = self.cols[next.rows[i] as usize];
+                cols[i] = self.rows[next.cols[i] as usize];
+            } else {
+                rows[i] = self.rows[next.rows[i] as usize];
+                cols[i] = self.cols[next.cols[i] as usize];
+            }
+        }
+
+        Transform {
+            transpose: self.transpose != next.transpose,
+            rows,
+            cols,
+            labels: self.labels.map(|d| next.labels[d as usize]),
+        }
+    }
+
+    /// Position in the original grid that ends up at `(x, y)`
+    #[inline]
+    pub fn source(&self, x: usize, y: usize) -> (usize, usize) {
+        let (cx, cy) = (self.cols[x] as usize, self.rows[y] as usize);
+        if self.transpose {
+            (cy, cx)
+        } else {
+            (cx, cy)
+        }
+    }
+
+    /// New label for `digit`, leaving the empty marker `9` alone
+    #[inline]
+    pub fn label(&self, digit: u8) -> u8 {
+        if digit == 9 {
+            9
+        } else {
+            self.labels[digit as usize]
+        }
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Grids that can be rearranged by a [`Transform`]
+pub trait Transformable {
+    fn transformed(&self, transform: &Transform) -> Self;
+}
+
+impl Transformable for Solution {
+    fn transformed(&self, transform: &Transform) -> Self {
+        let mut grid = Solution::new_empty();
+        for (y, x) in iproduct!(0..9, 0..9) {
+            let (sx, sy) = transform.source(x, y);
+            grid.set(x, y, transform.label(self.get(sx, sy)));
+        }
+        grid
+    }
+}
+
+impl Transformable for Possibilities {
+    fn transformed(&self, transform: &Transform) -> Self {
+        let mut grid = Possibilities::new_full();
+        for (y, x) in iproduct!(0..9, 0..9) {
+            let (sx, sy) = transform.source(x, y);
+            let source = self.get(sx, sy);
+
+            let mut cell = SudokuPossibility::new_empty();
+            for d in (0..9).filter(|d| source.has(*d)) {
+                cell = cell.or(SudokuPossibility::new_val(transform.label(d)));
+            }
+            grid.set(x, y, cell);
+        }
+        grid
+    }
+}
+
+fn is_permutation(values: &[u8]) -> bool {
+    let mut seen = 0u16;
+    for &v in values {
+        if v as usize >= values.len() || seen & (1 << v) != 0 {
+            return false;
+        }
+        seen |= 1 << v;
+    }
+    true
+}
+
+fn is_line_permutation(lines: &[u8; 9]) -> bool {
+    is_permutation(lines) && lines.chunks(3).all(|band| band.iter().all(|l| l / 3 == band[0] / 3))
+}
+
+fn lines_in_band(band: usize, order: [usize; 3]) -> [u8; 9] {
+    assert!(band < 3, "Band must be in 0..3");
+    let order = order.map(|o| o as u8);
+    assert!(is_permutation(&order), "Order must be a permutation of 0..3");
+
+    let mut lines = IDENTITY;
+    for i in 0..3 {
+        lines[band * 3 + order[i] as usize] = (band * 3 + i) as u8;
+    }
+    lines
+}
+
+fn bands(order: [usize; 3]) -> [u8; 9] {
+    let order = order.map(|o| o as u8);
+    assert!(is_permutation(&order), "Order must be a permutation of 0..3");
+
+    let mut lines = IDENTITY;
+    for i in 0..3 {
+        for j in 0..3 {
+            lines[order[i] as usize * 3 + j] = (i * 3 + j) as u8;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = ".2...69......5..2.6..3.....94...7......4..7...3.2...8...9.4....3..9.2.17..8.....2";
+    const SOLVED: &str = "425816973893754621617329548941687235582431769736295184279148356354962817168573492";
+
+    fn grid(line: &str) -> Solution {
+        line.parse().unwrap()
+    }
+
+    fn named() -> Vec<Transform> {
+        vec![
+            Transform::identity(),
+            Transform::rotate90(),
+            Transform::rotate180(),
+            Transform::rotate270(),
+            Transform::transpose(),
+            Transform::mirror_horizontal(),
+            Transform::mirror_vertical(),
+            Transform::permute_rows_in_band(1, [2, 0, 1]),
+            Transform::permute_bands([1, 2, 0]),
+            Transform::permute_columns_in_stack(2, [1, 0, 2]),
+            Transform::permute_stacks([2, 1, 0]),
+            Transform::relabel([3, 1, 4, 0, 5, 8, 2, 6, 7]),
+        ]
+    }
+
+    #[test]
+    fn rotate90_four_times_is_identity() {
+        let rotate = Transform::rotate90();
+        let full_turn = rotate.then(&rotate).then(&rotate).then(&rotate);
+        assert_eq!(full_turn, Transform::identity());
+
+        let puzzle = grid(PUZZLE);
+        let mut turned = puzzle.clone();
+        for _ in 0..4 {
+            turned = turned.transformed(&rotate);
+        }
+        assert_eq!(turned, puzzle);
+    }
+
+    #[test]
+    fn rotations_compose() {
+        let puzzle = grid(PUZZLE);
+        let rotate = Transform::rotate90();
+        assert_eq!(puzzle.transformed(&rotate.then(&rotate)), puzzle.transformed(&Transform::rotate180()));
+        assert_eq!(
+            puzzle.transformed(&Transform::rotate180().then(&rotate)),
+            puzzle.transformed(&Transform::rotate270())
+        );
+    }
+
+    #[test]
+    fn rotate90_is_clockwise() {
+        let solved = grid(SOLVED);
+        let rotated = solved.transformed(&Transform::rotate90());
+        for (y, x) in iproduct!(0..9, 0..9) {
+            assert_eq!(rotated.get(8 - y, x), solved.get(x, y));
+        }
+    }
+
+    #[test]
+    fn named_transforms_move_what_they_say() {
+        let solved = grid(SOLVED);
+        for (y, x) in iproduct!(0..9, 0..9) {
+            let val = solved.get(x, y);
+            assert_eq!(solved.transformed(&Transform::transpose()).get(y, x), val);
+            assert_eq!(solved.transformed(&Transform::mirror_horizontal()).get(x, 8 - y), val);
+            assert_eq!(solved.transformed(&Transform::mirror_vertical()).get(8 - x, y), val);
+        }
+
+        // Row 3 is the first of band 1, so moves to the band's last row
+        let permuted = solved.transformed(&Transform::permute_rows_in_band(1, [2, 0, 1]));
+        assert_eq!(permuted.get(0, 5), solved.get(0, 3));
+        let permuted = solved.transformed(&Transform::permute_bands([1, 2, 0]));
+        assert_eq!(permuted.get(0, 3), solved.get(0, 0));
+        let relabelled = solved.transformed(&Transform::relabel([3, 1, 4, 0, 5, 8, 2, 6, 7]));
+        assert_eq!(relabelled.get(0, 0), [3, 1, 4, 0, 5, 8, 2, 6, 7][solved.get(0, 0) as usize]);
+    }
+
+    #[test]
+    fn then_applies_in_order() {
+        let puzzle = grid(PUZZLE);
+        for (a, b) in iproduct!(named(), named()) {
+            assert_eq!(
+                puzzle.transformed(&a.then(&b)),
+                puzzle.transformed(&a).transformed(&b),
+                "{a:?} then {b:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn named_transforms_keep_grids_valid() {
+        let solved = grid(SOLVED);
+        for transform in named() {
+            let transformed = solved.transformed(&transform);
+            assert!(transformed.solved() && transformed.is_valid(), "{transform:?}");
+        }
+    }
+
+    #[test]
+    fn possibilities_follow_solution() {
+        let puzzle = grid(PUZZLE);
+        let transform = Transform::rotate90()
+            .then(&Transform::permute_stacks([2, 0, 1]))
+            .then(&Transform::relabel([8, 7, 6, 5, 4, 3, 2, 1, 0]));
+
+        let transformed = Possibilities::from_solution(&puzzle).transformed(&transform);
+        let expected = Possibilities::from_solution(&puzzle.transformed(&transform));
+        for (y, x) in iproduct!(0..9, 0..9) {
+            assert_eq!(transformed.get(x, y), expected.get(x, y), "r{}c{}", y + 1, x + 1);
+        }
+    }
+
+    #[test]
+    fn new_rejects_split_bands() {
+        assert!(Transform::new(false, IDENTITY, IDENTITY, IDENTITY).is_some());
+        assert!(Transform::new(false, [0, 1, 3, 2, 4, 5, 6, 7, 8], IDENTITY, IDENTITY).is_none());
+        assert!(Transform::new(false, IDENTITY, IDENTITY, [0, 0, 2, 3, 4, 5, 6, 7, 8]).is_none());
+    }
+}
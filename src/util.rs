@@ -1,29 +1,17 @@
-use std::fs;
-use crate::solution::Solution;
+use sudoku_solver_3::{PuzzleReader, Solution, SolutionColumn};
 
-pub fn load_puzzles(name: &str) -> Vec<(Solution, Option<Solution>)> {
-    let mut puzzles = Vec::new();
-    let mut i: usize = 0;
-    loop {
-        let Ok(ps) = fs::read_to_string(format!("converted/{name}_{i}_puzzle.txt")) else { break; };
-        let Ok(ss) = fs::read_to_string(format!("converted/{name}_{i}_solution.txt")) else { break; };
+pub fn load_puzzles(name: &str) -> Vec<(Solution, SolutionColumn)> {
+    let path = format!("converted/{name}.txt");
+    let reader = PuzzleReader::open(&path).unwrap_or_else(|e| panic!("Failed to open {path}: {e}"));
 
-        if ss.chars().next().unwrap() != '!' {
-            puzzles.push(
-                (Solution::load_string(ps), Some(Solution::load_string(ss)))
-            );
-        }
-        else {
-            puzzles.push(
-                (Solution::load_string(ps), None)
-            );
-        }
-
-
-        i += 1;
-    }
+    let puzzles = reader
+        .map(|entry| {
+            let entry = entry.unwrap_or_else(|e| panic!("Failed to load {path}: {e}"));
+            (entry.puzzle, entry.solution)
+        })
+        .collect::<Vec<_>>();
 
-    println!("Loaded {i} {name} puzzles");
+    println!("Loaded {} {name} puzzles", puzzles.len());
 
     puzzles
 }
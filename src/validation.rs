@@ -0,0 +1,104 @@
+use std::fmt::{Display, Formatter};
+
+/// A row, column or box of the grid, indexed from 0.
+///
+/// Boxes are numbered left to right, top to bottom.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Unit {
+    Row(usize),
+    Column(usize),
+    Box(usize),
+}
+
+impl Unit {
+    pub fn all() -> impl Iterator<Item = Unit> {
+        (0..9)
+            .map(Unit::Row)
+            .chain((0..9).map(Unit::Column))
+            .chain((0..9).map(Unit::Box))
+    }
+
+    /// The `(x, y)` positions of the unit's cells in reading order
+    pub fn cells(self) -> [(usize, usize); 9] {
+        let mut cells = [(0, 0); 9];
+        for (i, cell) in cells.iter_mut().enumerate() {
+            *cell = match self {
+                Unit::Row(y) => (i, y),
+                Unit::Column(x) => (x, i),
+                Unit::Box(b) => ((b % 3) * 3 + i % 3, (b / 3) * 3 + i / 3),
+            };
+        }
+        cells
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unit::Row(y) => write!(f, "row {}", y + 1),
+            Unit::Column(x) => write!(f, "column {}", x + 1),
+            Unit::Box(b) => write!(f, "box {}", b + 1),
+        }
+    }
+}
+
+/// A digit given more than once within a unit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Duplicate {
+    pub digit: u8,
+    pub unit: Unit,
+    /// `(x, y)` positions of every occurrence of `digit` in `unit`
+    pub cells: Vec<(usize, usize)>,
+}
+
+impl Display for Duplicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} appears {} times in {} at", self.digit + 1, self.cells.len(), self.unit)?;
+        for (i, (x, y)) in self.cells.iter().enumerate() {
+            if i != 0 {
+                write!(f, ",")?;
+            }
+            write!(f, " r{}c{}", y + 1, x + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub duplicates: Vec<Duplicate>,
+}
+
+impl ValidationReport {
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.duplicates.is_empty()
+    }
+
+    /// Every cell involved in a duplicate, in reading order without repeats
+    pub fn conflicting_cells(&self) -> Vec<(usize, usize)> {
+        let mut cells = self
+            .duplicates
+            .iter()
+            .flat_map(|d| d.cells.iter().copied())
+            .collect::<Vec<_>>();
+        cells.sort_by_key(|&(x, y)| (y, x));
+        cells.dedup();
+        cells
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_valid() {
+            return write!(f, "No conflicts");
+        }
+        for (i, duplicate) in self.duplicates.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{duplicate}")?;
+        }
+        Ok(())
+    }
+}